@@ -1,25 +1,22 @@
-use ron::{ser::to_string_pretty, Value};
-use ron_reboot::from_str_serde;
+use ron_reboot::{Files, Value};
 
 fn main() {
-    let s = std::fs::read_to_string(std::env::args().nth(1).unwrap()).expect("file not found");
+    let path = std::env::args().nth(1).unwrap();
+    let s = std::fs::read_to_string(&path).expect("file not found");
 
-    match from_str_serde::<Value>(&s) {
+    let mut files = Files::new();
+    let id = files.add(path, s);
+
+    match files.source(id).parse::<Value>() {
         Ok(x) => {
-            println!("{}", to_string_pretty(&x, Default::default()).unwrap());
+            println!("{:#}", x);
 
             println!();
             println!("Debug:");
             println!("{:#?}", x);
         }
         Err(e) => {
-            let mut e = &e as &dyn std::error::Error;
-            eprintln!("{}", e);
-
-            while let Some(s) = e.source() {
-                eprintln!("  caused by: {}", s);
-                e = s;
-            }
+            files.print_error(&e.context_file(id)).unwrap();
         }
     }
 }
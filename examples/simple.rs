@@ -1,4 +1,4 @@
-use ron_reboot::{from_str_serde, print_error};
+use ron_reboot::{from_str_serde, Files};
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -9,15 +9,19 @@ pub struct MyStruct {
 }
 
 fn main() {
-    let s = std::fs::read_to_string(std::env::args().nth(1).unwrap()).expect("file not found");
+    let path = std::env::args().nth(1).unwrap();
+    let s = std::fs::read_to_string(&path).expect("file not found");
 
-    match from_str_serde::<MyStruct>(&s) {
+    let mut files = Files::new();
+    let id = files.add(path, s);
+
+    match from_str_serde::<MyStruct>(files.source(id)) {
         Ok(x) => {
             println!("Debug:");
             println!("{:#?}", x);
         }
         Err(e) => {
-            print_error(&e).unwrap();
+            files.print_error(&e.context_file(id)).unwrap();
         }
     }
 }
@@ -1,7 +1,6 @@
-use ron_reboot::{print_error, Error, Value};
+use ron_reboot::{Error, Files, Value};
 
-fn app() -> Result<(), Error> {
-    let value: Value = r##"
+const CONFIG: &str = r##"
 MyConfig (
     accurate: Types(are: "awesome"),
     even: [r#"tuples", "and", "lists"],
@@ -11,8 +10,10 @@ MyConfig (
         "it's": true,
     },
 )
-    "##
-    .parse()?;
+"##;
+
+fn app(source: &str) -> Result<(), Error> {
+    let value: Value = source.parse()?;
 
     match value {
         Value::Struct(Some(ident), fields) => {
@@ -29,7 +30,10 @@ MyConfig (
 }
 
 fn main() {
-    if let Err(e) = app() {
-        print_error(&e).unwrap();
+    let mut files = Files::new();
+    let id = files.add("config.ron", CONFIG);
+
+    if let Err(e) = app(files.source(id)) {
+        files.print_error(&e.context_file(id)).unwrap();
     }
 }
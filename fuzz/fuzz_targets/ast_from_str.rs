@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ron_reboot::utf8_parser::ast_from_str;
+
+fuzz_target!(|input: &str| {
+    // Errors are fine, panics and hangs are not; rendering the error
+    // converts all of its locations
+    if let Err(e) = ast_from_str(input) {
+        let _ = e.to_string();
+    }
+});
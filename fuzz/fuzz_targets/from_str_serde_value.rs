@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ron_reboot::{from_str_serde, Value};
+
+fuzz_target!(|input: &str| {
+    if let Err(e) = from_str_serde::<Value>(input) {
+        let _ = e.to_string();
+    }
+});
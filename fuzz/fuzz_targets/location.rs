@@ -0,0 +1,25 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ron_reboot::{utf8_parser::test_util::location_at, Location};
+
+fuzz_target!(|data: (&str, usize)| {
+    let (input, offset) = data;
+    let offset = offset % (input.len() + 1);
+    if !input.is_char_boundary(offset) {
+        return;
+    }
+
+    let mut expected = Location { line: 1, column: 1 };
+    for c in input[..offset].chars() {
+        match c {
+            '\n' => {
+                expected.line += 1;
+                expected.column = 1;
+            }
+            _ => expected.column += 1,
+        }
+    }
+
+    assert_eq!(location_at(input, offset), expected);
+});
@@ -0,0 +1,154 @@
+//! Compile-time checked RON for [`ron-reboot`](https://docs.rs/ron-reboot)
+//!
+//! Both macros parse the RON while compiling, so syntax errors are reported by
+//! `cargo build` instead of at runtime:
+//!
+//! ```compile_fail
+//! const CONFIG: &str = ron_reboot_macros::ron!("(width: 800, height: 600");
+//! ```
+//!
+//! Without a type, they expand to a `&'static str` with the checked source.
+//! With a type, they expand to `ron_reboot::from_str_serde::<T>(..)`, which
+//! returns a `Result` because the structure is only checked against `T` at runtime:
+//!
+//! ```
+//! use ron_reboot_macros::ron;
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize, PartialEq)]
+//! struct Window {
+//!     width: u32,
+//!     height: u32,
+//! }
+//!
+//! const SOURCE: &str = ron!("(width: 800, height: 600)");
+//!
+//! let window = ron!(Window, "(width: 800, height: 600)").unwrap();
+//! assert_eq!(window, Window { width: 800, height: 600 });
+//! assert_eq!(SOURCE, "(width: 800, height: 600)");
+//! ```
+
+use std::{env, fs::read_to_string, path::PathBuf};
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use ron_reboot::{utf8_parser::ast_from_str, Error, Files};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, LitStr, Token, Type,
+};
+
+/// Checks the RON string literal at compile time.
+///
+/// * `ron!("..")` expands to the string literal
+/// * `ron!(Type, "..")` expands to `ron_reboot::from_str_serde::<Type>("..")`
+#[proc_macro]
+pub fn ron(input: TokenStream) -> TokenStream {
+    let Input { ty, lit } = parse_macro_input!(input as Input);
+
+    match check(&lit.value(), None) {
+        Ok(()) => expand(ty, quote!(#lit)),
+        Err(message) => syn::Error::new(lit.span(), message)
+            .to_compile_error()
+            .into(),
+    }
+}
+
+/// Like [`ron!`], but reads the RON from a file.
+///
+/// The path is relative to the `Cargo.toml` of the crate using the macro.
+/// The file is included with `include_str!`, so changes to it trigger a rebuild.
+///
+/// * `include_ron!("..")` expands to the file's content
+/// * `include_ron!(Type, "..")` expands to `ron_reboot::from_str_serde::<Type>(..)`
+#[proc_macro]
+pub fn include_ron(input: TokenStream) -> TokenStream {
+    let Input { ty, lit } = parse_macro_input!(input as Input);
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = PathBuf::from(manifest_dir).join(lit.value());
+    let checked = read_to_string(&path)
+        .map_err(|e| format!("couldn't read {}: {}", path.display(), e))
+        .and_then(|source| check(&source, Some(lit.value())));
+
+    match (checked, path.to_str()) {
+        (Ok(()), Some(path)) => expand(ty, quote!(include_str!(#path))),
+        (Ok(()), None) => syn::Error::new(lit.span(), "path is not valid UTF-8")
+            .to_compile_error()
+            .into(),
+        (Err(message), _) => syn::Error::new(lit.span(), message)
+            .to_compile_error()
+            .into(),
+    }
+}
+
+/// Arguments of both macros: `[Type,] "literal"`
+struct Input {
+    ty: Option<Type>,
+    lit: LitStr,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = match input.peek(LitStr) {
+            true => None,
+            false => {
+                let ty = input.parse()?;
+                input.parse::<Token![,]>()?;
+
+                Some(ty)
+            }
+        };
+        let lit = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+
+        Ok(Input { ty, lit })
+    }
+}
+
+fn expand(ty: Option<Type>, source: TokenStream2) -> TokenStream {
+    match ty {
+        None => source,
+        Some(ty) => quote!(::ron_reboot::from_str_serde::<#ty>(#source)),
+    }
+    .into()
+}
+
+/// Parses `source`, returning the rendered error on failure
+fn check(source: &str, file_name: Option<String>) -> Result<(), String> {
+    let mut files = Files::new();
+    let id = files.add(file_name.as_deref().unwrap_or("string"), source);
+
+    ast_from_str(source)
+        .map(|_| ())
+        .map_err(|e| render(&files, e.context_file(id)))
+}
+
+fn render(files: &Files, e: Error) -> String {
+    let mut rendered = vec![];
+
+    // `compile_error!` adds its own `error: ` header
+    match files.write_error(&mut rendered, &e) {
+        Ok(()) => String::from_utf8_lossy(&rendered)
+            .trim_start_matches("error: ")
+            .trim_end()
+            .to_owned(),
+        Err(_) => e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check;
+
+    #[test]
+    fn check_renders_error() {
+        assert_eq!(check("(a: [1, 2])", None), Ok(()));
+
+        let message = check("(a: [1, 2)", Some("config.ron".to_owned())).unwrap_err();
+        assert!(message.starts_with("parsing error: "), "{}", message);
+        assert!(message.contains("--> config.ron:1:"), "{}", message);
+        assert!(message.contains("1 | (a: [1, 2)"), "{}", message);
+    }
+}
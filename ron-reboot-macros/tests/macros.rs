@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+
+use ron_reboot_macros::{include_ron, ron};
+use serde::Deserialize;
+
+const BIG_CONFIG: &str = include_ron!("../tests/big_config.ron");
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Data {
+    string: String,
+    int: i32,
+}
+
+#[test]
+fn string_literal() {
+    assert_eq!(ron!("(a: 1)"), "(a: 1)");
+    assert_eq!(ron!(r#"[Some("raw")]"#,), r#"[Some("raw")]"#);
+}
+
+#[test]
+fn deserialize_literal() {
+    assert_eq!(
+        ron!(Data, r#"(string: "s", int: 3)"#).unwrap(),
+        Data {
+            string: "s".to_owned(),
+            int: 3
+        }
+    );
+    assert_eq!(
+        ron!(Vec<Option<u8>>, "[Some(1), None]").unwrap(),
+        vec![Some(1), None]
+    );
+
+    // syntax is checked at compile time, types at runtime
+    assert!(ron!(Data, "(string: 1, int: 3)").is_err());
+}
+
+#[test]
+fn include_file() {
+    assert_eq!(BIG_CONFIG, include_str!("../../tests/big_config.ron"));
+
+    let mapping = include_ron!(
+        HashMap<String, HashMap<String, Data>>,
+        "tests/mapping.ron"
+    )
+    .unwrap();
+    assert_eq!(mapping["a"]["b"].int, 1);
+}
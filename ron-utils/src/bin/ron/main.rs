@@ -1,6 +1,6 @@
 use std::process::exit;
 
-use ron_utils::validate_file;
+use ron_utils::{lint_file, validate_file, Files, Level, LintConfig, Rule};
 use structopt::StructOpt;
 
 use crate::print_opt::PrintOpt;
@@ -23,6 +23,21 @@ enum Opt {
         /// The .ron files to validate
         files: Vec<String>,
     },
+    /// Check .ron file(s) for likely mistakes
+    Lint {
+        #[structopt(long, short = "A", number_of_values = 1)]
+        /// Don't check the given rule
+        allow: Vec<Rule>,
+        #[structopt(long, short = "W", number_of_values = 1)]
+        /// Report violations of the given rule as warnings
+        warn: Vec<Rule>,
+        #[structopt(long, short = "D", number_of_values = 1)]
+        /// Report violations of the given rule as errors
+        deny: Vec<Rule>,
+        #[structopt(required = true)]
+        /// The .ron files to lint
+        files: Vec<String>,
+    },
 }
 
 fn main() {
@@ -53,6 +68,45 @@ fn main() {
                 }
             }
 
+            if error {
+                exit(1);
+            }
+        }
+        Opt::Lint {
+            allow,
+            warn,
+            deny,
+            files,
+        } => {
+            let mut config = LintConfig::default();
+            for (rules, level) in [
+                (allow, Level::Allow),
+                (warn, Level::Warn),
+                (deny, Level::Deny),
+            ] {
+                for rule in rules {
+                    config.set_level(rule, level);
+                }
+            }
+
+            let mut error = false;
+            let mut sources = Files::new();
+
+            for file in &files {
+                match lint_file(&mut sources, file, &config) {
+                    Ok(diagnostics) => {
+                        for diagnostic in &diagnostics {
+                            let _ = diagnostic.print_with_files(&sources);
+                            error |= diagnostic.is_error();
+                        }
+                    }
+                    Err(e) => {
+                        let _ = sources.print_error(&e);
+                        error = true;
+                    }
+                }
+            }
+
             if error {
                 exit(1);
             }
@@ -1,14 +1,48 @@
 use std::{fs::read_to_string, path::Path};
 
 use ron_reboot::utf8_parser::ast_from_str;
-pub use ron_reboot::{print_error, Error};
+pub use ron_reboot::{
+    lint::{Diagnostic, Level, LintConfig, Rule},
+    print_error, Error, Files,
+};
 
 pub fn validate_str(s: &str) -> Result<(), ron_reboot::Error> {
     ast_from_str(s).map(|_| ())
 }
 
 pub fn validate_file(p: impl AsRef<Path>) -> Result<(), ron_reboot::Error> {
-    ast_from_str(&read_fs_string(p)?).map(|_| ())
+    let s = read_fs_string(p)?;
+    let result = ast_from_str(&s).map(|_| ());
+
+    result.map_err(|e| e.context_file_content(s))
+}
+
+pub fn lint_str(s: &str, config: &LintConfig) -> Result<Vec<Diagnostic>, ron_reboot::Error> {
+    let ron = ast_from_str(s)?;
+
+    Ok(ron_reboot::lint::lint(&ron, config))
+}
+
+/// Adds the file to `files` and lints it, the diagnostics point into `files`
+pub fn lint_file(
+    files: &mut Files,
+    p: impl AsRef<Path>,
+    config: &LintConfig,
+) -> Result<Vec<Diagnostic>, ron_reboot::Error> {
+    let path = p.as_ref();
+    let id = files.add(path.display().to_string(), read_fs_string(path)?);
+
+    lint_str(files.source(id), config)
+        .map(|diagnostics| {
+            diagnostics
+                .into_iter()
+                .map(|d| Diagnostic {
+                    error: d.error.context_file(id),
+                    ..d
+                })
+                .collect()
+        })
+        .map_err(|e| e.context_file(id))
 }
 
 #[cfg(feature = "serde1")]
@@ -1,19 +1,23 @@
-use std::mem::replace;
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+    mem::replace,
+};
 
 #[cfg(feature = "serde1_ast_derives")]
 use serde::Serialize;
 
 use crate::location::Location;
 
+/// A value and its location, `end` is exclusive
+///
+/// Also usable as field type when deserializing, to keep the locations for later checks.
+///
 /// IMPORTANT: Equality operators do NOT compare the start & end spans!
 #[derive(Clone, Debug)]
-#[cfg_attr(feature = "serde1_ast_derives", derive(Serialize))]
-#[cfg_attr(feature = "serde1_ast_derives", serde(transparent))]
 pub struct Spanned<T> {
-    #[cfg_attr(feature = "serde1_ast_derives", serde(skip))]
     pub start: Location,
     pub value: T,
-    #[cfg_attr(feature = "serde1_ast_derives", serde(skip))]
     pub end: Location,
 }
 
@@ -67,6 +71,22 @@ impl Attribute {
 pub enum Extension {
     UnwrapNewtypes,
     ImplicitSome,
+    /// The document consists of the fields of a struct, without the surrounding `(` and `)`
+    ImplicitOuterStruct,
+    /// Map keys may be bare identifiers, which deserialize like strings
+    UnquotedMapKeys,
+}
+
+impl Extension {
+    /// The name used in `#![enable(...)]`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Extension::UnwrapNewtypes => "unwrap_newtypes",
+            Extension::ImplicitSome => "implicit_some",
+            Extension::ImplicitOuterStruct => "implicit_outer_struct",
+            Extension::UnquotedMapKeys => "unquoted_map_keys",
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -184,6 +204,14 @@ impl Integer {
     pub fn to_expr(self) -> Expr<'static> {
         Expr::Integer(self)
     }
+
+    /// The value of the integer, `i128` fits all of them
+    fn to_i128(&self) -> i128 {
+        match self {
+            Integer::Signed(s) => s.sign.into_i8() as i128 * s.number as i128,
+            Integer::Unsigned(u) => u.number as i128,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -216,19 +244,128 @@ impl Decimal {
 
 impl From<Decimal> for f64 {
     fn from(d: Decimal) -> f64 {
-        let sign = d.sign.map(Into::into).unwrap_or(1i8);
-        let whole = d.whole.unwrap_or_default();
+        // Rust's float parsing rounds correctly, summing up the parts would not.
+        // The decimal is written as `<digits>e<exponent>` to a buffer on the stack for it.
+        let mut text = DecimalText {
+            bytes: [0; DecimalText::CAPACITY],
+            len: 0,
+        };
+
+        if d.sign == Some(Sign::Negative) {
+            text.push(b'-');
+        }
+
+        let mut exponent = match d.exponent {
+            Some((Some(Sign::Negative), exp)) => -(exp as i32),
+            Some((_, exp)) => exp as i32,
+            None => 0,
+        } - d.fractional_digits as i32;
+
+        let mut digits = [0; 20];
+        let whole = match d.whole {
+            Some(whole) if whole != 0 => ascii_digits(whole, &mut digits),
+            _ => &[],
+        };
+        let mut fractional_digits = [0; 20];
+        let fractional = ascii_digits(d.fractional, &mut fractional_digits);
+        // Leading zeros of the fractional part only count after a whole part
+        let zeros = match whole.is_empty() {
+            true => 0,
+            false => d.fractional_digits as usize - fractional.len(),
+        };
+
+        let mut all_digits = whole
+            .iter()
+            .copied()
+            .chain(std::iter::repeat_n(b'0', zeros))
+            .chain(fractional.iter().copied());
+        for digit in all_digits.by_ref().take(DecimalText::MAX_DIGITS) {
+            text.push(digit);
+        }
+        if whole.is_empty() && fractional.is_empty() {
+            text.push(b'0');
+        }
+
+        // More digits than needed for rounding, the rest only matters if it's zero or not
+        let mut rest = 0;
+        let mut rest_is_zero = true;
+        for digit in all_digits {
+            rest += 1;
+            rest_is_zero &= digit == b'0';
+        }
+        if rest != 0 {
+            text.push(if rest_is_zero { b'0' } else { b'1' });
+            exponent += rest - 1;
+        }
+
+        text.push(b'e');
+        if exponent < 0 {
+            text.push(b'-');
+        }
+        let mut exponent_digits = [0; 20];
+        for &digit in ascii_digits(exponent.unsigned_abs() as u64, &mut exponent_digits) {
+            text.push(digit);
+        }
+        if exponent == 0 {
+            text.push(b'0');
+        }
+
+        // Only ASCII digits, `-` and `e` are written, so this doesn't fall back
+        std::str::from_utf8(&text.bytes[..text.len])
+            .ok()
+            .and_then(|text| text.parse().ok())
+            .unwrap_or(f64::NAN)
+    }
+}
+
+/// The text of a decimal, see `From<Decimal> for f64`
+struct DecimalText {
+    bytes: [u8; DecimalText::CAPACITY],
+    len: usize,
+}
 
-        let (exp_sign, exp) = d.exponent.unwrap_or((None, 0));
+impl DecimalText {
+    /// Rounding a decimal needs at most 768 significant digits (and whether more follow)
+    const MAX_DIGITS: usize = 800;
+    /// Sign, digits, the one after them & the exponent (`e-131070`)
+    const CAPACITY: usize = 1 + Self::MAX_DIGITS + 1 + 8;
 
-        let exp_sign = exp_sign.map(Into::into).unwrap_or(1i8);
-        let exp = exp as i32 * exp_sign as i32;
+    fn push(&mut self, byte: u8) {
+        self.bytes[self.len] = byte;
+        self.len += 1;
+    }
+}
 
-        let mut f: f64 = sign as f64 * whole as f64;
-        f *= (10.0f64).powi(exp);
-        f += d.fractional as f64 * (10.0f64).powi(exp - d.fractional_digits as i32) * sign as f64;
+/// The decimal digits of `n` as ASCII, `0` has no digits
+fn ascii_digits(mut n: u64, buffer: &mut [u8; 20]) -> &[u8] {
+    let mut start = buffer.len();
 
-        f
+    while n != 0 {
+        start -= 1;
+        buffer[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+    }
+
+    &buffer[start..]
+}
+
+/// A float without decimal notation
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1_ast_derives", derive(Serialize))]
+pub enum NonFinite {
+    /// `inf`, `+inf` or `-inf`
+    Infinity(Option<Sign>),
+    /// `NaN`
+    NaN,
+}
+
+impl From<NonFinite> for f64 {
+    fn from(n: NonFinite) -> f64 {
+        match n {
+            NonFinite::Infinity(Some(Sign::Negative)) => f64::NEG_INFINITY,
+            NonFinite::Infinity(_) => f64::INFINITY,
+            NonFinite::NaN => f64::NAN,
+        }
     }
 }
 
@@ -251,12 +388,76 @@ impl<'a, K: 'a> KeyValue<'a, K> {
 
 pub type SpannedKvs<'a, K> = Vec<Spanned<KeyValue<'a, K>>>;
 
+/// Keys of structs and maps, compared by what they deserialize to
+trait Key {
+    fn same_key(&self, other: &Self) -> bool;
+
+    /// Keys which are the same have to hash the same
+    fn hash_key<H: Hasher>(&self, state: &mut H);
+}
+
+impl Key for Ident<'_> {
+    fn same_key(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    fn hash_key<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+/// A key in a hash set, compared with [`Key::same_key`] and without its span
+struct ByKey<'b, K>(&'b Spanned<K>);
+
+impl<K: Key> PartialEq for ByKey<'_, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.value.same_key(&other.0.value)
+    }
+}
+
+impl<K: Key> Eq for ByKey<'_, K> {}
+
+impl<K: Key> Hash for ByKey<'_, K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.value.hash_key(state);
+    }
+}
+
+/// Returns `(first, duplicate)` key pairs for every key that occurs more than once.
+/// Keys are compared without their spans.
+fn duplicate_keys<'b, K: Key>(
+    kvs: &'b [Spanned<KeyValue<K>>],
+) -> Vec<(&'b Spanned<K>, &'b Spanned<K>)> {
+    let mut firsts: HashSet<ByKey<K>> = HashSet::with_capacity(kvs.len());
+
+    kvs.iter()
+        .filter_map(|kv| {
+            let key = ByKey(&kv.value.key);
+
+            match firsts.get(&key) {
+                Some(first) => Some((first.0, key.0)),
+                None => {
+                    firsts.insert(key);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde1_ast_derives", derive(Serialize))]
 pub struct Struct<'a> {
     pub fields: SpannedKvs<'a, Ident<'a>>,
 }
 
+impl<'a> Struct<'a> {
+    /// Field names which are defined more than once, as `(first, duplicate)` pairs
+    pub fn duplicate_fields(&self) -> Vec<(&Spanned<Ident<'a>>, &Spanned<Ident<'a>>)> {
+        duplicate_keys(&self.fields)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde1_ast_derives", derive(Serialize))]
 pub struct Map<'a> {
@@ -264,6 +465,14 @@ pub struct Map<'a> {
 }
 
 impl<'a> Map<'a> {
+    /// Keys which occur more than once, as `(first, duplicate)` pairs
+    ///
+    /// Keys are compared by the value they deserialize to, so `"a"` and `"\u{61}"`,
+    /// `1` and `+1` or `a` and `"a"` (with `unquoted_map_keys`) are duplicates.
+    pub fn duplicate_keys(&self) -> Vec<(&Spanned<Expr<'a>>, &Spanned<Expr<'a>>)> {
+        duplicate_keys(&self.entries)
+    }
+
     #[cfg(test)]
     pub fn new_test(kvs: Vec<(Expr<'a>, Expr<'a>)>) -> Self {
         Map {
@@ -337,7 +546,119 @@ pub enum Expr<'a> {
     Str(&'a str),
     /// Escaped string
     String(String),
+    Char(char),
     Decimal(Decimal),
+    /// `inf`, `-inf` or `NaN`
+    NonFinite(NonFinite),
+    /// Map key written as a bare identifier (`unquoted_map_keys` extension)
+    UnquotedKey(Ident<'a>),
+}
+
+impl Key for Expr<'_> {
+    /// Whether both expressions deserialize to the same map key,
+    /// e.g. `"a"` and `"\u{61}"` or `1` and `+1`
+    fn same_key(&self, other: &Self) -> bool {
+        match (self.key_str(), other.key_str()) {
+            (Some(a), Some(b)) => return a == b,
+            (Some(_), None) | (None, Some(_)) => return false,
+            (None, None) => {}
+        }
+
+        match (self, other) {
+            (Expr::Integer(a), Expr::Integer(b)) => a.to_i128() == b.to_i128(),
+            (Expr::Decimal(a), Expr::Decimal(b)) => f64::from(a.clone()) == f64::from(b.clone()),
+            (Expr::NonFinite(a), Expr::NonFinite(b)) => f64::from(*a) == f64::from(*b),
+            (Expr::Optional(Some(a)), Expr::Optional(Some(b))) => a.value.same_key(&b.value),
+            (Expr::Tuple(Tuple { elements: a }), Expr::Tuple(Tuple { elements: b }))
+            | (Expr::List(List { elements: a }), Expr::List(List { elements: b }))
+            | (Expr::Tuple(Tuple { elements: a }), Expr::List(List { elements: b }))
+            | (Expr::List(List { elements: a }), Expr::Tuple(Tuple { elements: b })) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.value.same_key(&b.value))
+            }
+            (a, b) => a == b,
+        }
+    }
+
+    /// Hashes the parts [`Key::same_key`] compares, as well as those `==` compares
+    /// for the remaining expressions
+    fn hash_key<H: Hasher>(&self, state: &mut H) {
+        if let Some(s) = self.key_str() {
+            s.hash(state);
+            return;
+        }
+
+        // tuples and lists with the same elements are the same key
+        if let Expr::Tuple(Tuple { elements }) | Expr::List(List { elements }) = self {
+            return hash_elements(elements, state);
+        }
+
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Expr::Integer(i) => i.to_i128().hash(state),
+            Expr::Decimal(d) => float_key(f64::from(d.clone())).hash(state),
+            Expr::NonFinite(n) => float_key(f64::from(*n)).hash(state),
+            Expr::Optional(Some(e)) => e.value.hash_key(state),
+            Expr::Bool(b) => b.hash(state),
+            Expr::Char(c) => c.hash(state),
+            Expr::Tagged(Tagged { ident, untagged }) => {
+                ident.value.0.hash(state);
+                match &untagged.value {
+                    Untagged::Unit => {}
+                    Untagged::Struct(s) => hash_fields(&s.fields, state),
+                    Untagged::Tuple(t) => hash_elements(&t.elements, state),
+                }
+            }
+            Expr::Struct(s) => hash_fields(&s.fields, state),
+            Expr::Map(m) => {
+                for kv in &m.entries {
+                    kv.value.key.value.hash_key(state);
+                    kv.value.value.value.hash_key(state);
+                }
+            }
+            Expr::Unit
+            | Expr::Optional(None)
+            | Expr::Tuple(_)
+            | Expr::List(_)
+            | Expr::Str(_)
+            | Expr::String(_)
+            | Expr::UnquotedKey(_) => {}
+        }
+    }
+}
+
+fn hash_elements<H: Hasher>(elements: &[Spanned<Expr>], state: &mut H) {
+    elements.len().hash(state);
+    for element in elements {
+        element.value.hash_key(state);
+    }
+}
+
+fn hash_fields<H: Hasher>(fields: &[Spanned<KeyValue<Ident>>], state: &mut H) {
+    fields.len().hash(state);
+    for field in fields {
+        field.value.key.value.0.hash(state);
+        field.value.value.value.hash_key(state);
+    }
+}
+
+/// The bits of `f`, with `-0.0` like `0.0` since they are equal
+fn float_key(f: f64) -> u64 {
+    match f == 0.0 {
+        true => 0.0f64.to_bits(),
+        false => f.to_bits(),
+    }
+}
+
+impl<'a> Expr<'a> {
+    /// The string a string key deserializes to, including unquoted keys
+    fn key_str(&self) -> Option<&str> {
+        match self {
+            Expr::Str(s) => Some(s),
+            Expr::String(s) => Some(s),
+            Expr::UnquotedKey(k) => Some(k.0),
+            _ => None,
+        }
+    }
 }
 
 impl<'a> Expr<'a> {
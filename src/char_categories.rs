@@ -54,30 +54,94 @@ const ENCODINGS: [u8; 256] = [
     /* 250+: ·········· */ _____, _____, _____, _____, _____, _____
 ];
 
+/// Looks up `category` in the table, which only covers ASCII
+const fn is_ascii_in(c: char, category: u8) -> bool {
+    c.is_ascii() && ENCODINGS[c as usize] & category != 0
+}
+
 pub const fn is_digit_first(c: char) -> bool {
-    ENCODINGS[c as u8 as usize] & DIGIT_FIRST != 0
+    is_ascii_in(c, DIGIT_FIRST)
 }
 
 pub const fn is_digit(c: char) -> bool {
-    ENCODINGS[c as u8 as usize] & DIGIT_ANY != 0
+    is_ascii_in(c, DIGIT_ANY)
 }
 
 pub const fn is_ws(c: char) -> bool {
-    ENCODINGS[c as u8 as usize] & WHITESPACE_CHAR != 0
+    is_ascii_in(c, WHITESPACE_CHAR)
 }
 
-pub const fn is_ident_first_char(c: char) -> bool {
-    ENCODINGS[c as u8 as usize] & IDENT_FIRST_CHAR != 0
+/// `_` or XID_Start, like Rust identifiers
+pub fn is_ident_first_char(c: char) -> bool {
+    match c.is_ascii() {
+        true => is_ascii_in(c, IDENT_FIRST_CHAR),
+        false => unicode_ident::is_xid_start(c),
+    }
 }
 
-pub const fn is_ident_other_char(c: char) -> bool {
-    ENCODINGS[c as u8 as usize] & IDENT_OTHER_CHAR != 0
+/// XID_Continue, like Rust identifiers
+pub fn is_ident_other_char(c: char) -> bool {
+    match c.is_ascii() {
+        true => is_ascii_in(c, IDENT_OTHER_CHAR),
+        false => unicode_ident::is_xid_continue(c),
+    }
 }
 
-pub const fn is_ident_raw_char(c: char) -> bool {
-    ENCODINGS[c as u8 as usize] & IDENT_RAW_CHAR != 0
+/// XID_Continue and `.+-`
+pub fn is_ident_raw_char(c: char) -> bool {
+    match c.is_ascii() {
+        true => is_ascii_in(c, IDENT_RAW_CHAR),
+        false => unicode_ident::is_xid_continue(c),
+    }
 }
 
-const fn is_whitespace_char(c: char) -> bool {
-    ENCODINGS[c as u8 as usize] & WHITESPACE_CHAR != 0
+/// Non-ASCII chars which are easily mistaken for RON syntax,
+/// with the ASCII char they look like and their Unicode name
+const CONFUSABLES: &[(char, char, &str)] = &[
+    ('\u{00A0}', ' ', "NO-BREAK SPACE"),
+    ('\u{2000}', ' ', "EN QUAD"),
+    ('\u{2002}', ' ', "EN SPACE"),
+    ('\u{2003}', ' ', "EM SPACE"),
+    ('\u{2009}', ' ', "THIN SPACE"),
+    ('\u{200B}', ' ', "ZERO WIDTH SPACE"),
+    ('\u{3000}', ' ', "IDEOGRAPHIC SPACE"),
+    ('\u{FEFF}', ' ', "ZERO WIDTH NO-BREAK SPACE"),
+    ('\u{201C}', '"', "LEFT DOUBLE QUOTATION MARK"),
+    ('\u{201D}', '"', "RIGHT DOUBLE QUOTATION MARK"),
+    ('\u{201E}', '"', "DOUBLE LOW-9 QUOTATION MARK"),
+    ('\u{201F}', '"', "DOUBLE HIGH-REVERSED-9 QUOTATION MARK"),
+    ('\u{FF02}', '"', "FULLWIDTH QUOTATION MARK"),
+    ('\u{2018}', '\'', "LEFT SINGLE QUOTATION MARK"),
+    ('\u{2019}', '\'', "RIGHT SINGLE QUOTATION MARK"),
+    ('\u{FF07}', '\'', "FULLWIDTH APOSTROPHE"),
+    ('\u{FF08}', '(', "FULLWIDTH LEFT PARENTHESIS"),
+    ('\u{FF09}', ')', "FULLWIDTH RIGHT PARENTHESIS"),
+    ('\u{FF3B}', '[', "FULLWIDTH LEFT SQUARE BRACKET"),
+    ('\u{FF3D}', ']', "FULLWIDTH RIGHT SQUARE BRACKET"),
+    ('\u{FF5B}', '{', "FULLWIDTH LEFT CURLY BRACKET"),
+    ('\u{FF5D}', '}', "FULLWIDTH RIGHT CURLY BRACKET"),
+    ('\u{FF0C}', ',', "FULLWIDTH COMMA"),
+    ('\u{3001}', ',', "IDEOGRAPHIC COMMA"),
+    ('\u{201A}', ',', "SINGLE LOW-9 QUOTATION MARK"),
+    ('\u{FF1A}', ':', "FULLWIDTH COLON"),
+    ('\u{2236}', ':', "RATIO"),
+    ('\u{FF0E}', '.', "FULLWIDTH FULL STOP"),
+    ('\u{2024}', '.', "ONE DOT LEADER"),
+    ('\u{2212}', '-', "MINUS SIGN"),
+    ('\u{2010}', '-', "HYPHEN"),
+    ('\u{2013}', '-', "EN DASH"),
+    ('\u{2014}', '-', "EM DASH"),
+    ('\u{FF0D}', '-', "FULLWIDTH HYPHEN-MINUS"),
+    ('\u{FF0B}', '+', "FULLWIDTH PLUS SIGN"),
+    ('\u{FF03}', '#', "FULLWIDTH NUMBER SIGN"),
+    ('\u{FF01}', '!', "FULLWIDTH EXCLAMATION MARK"),
+    ('\u{FF3F}', '_', "FULLWIDTH LOW LINE"),
+];
+
+/// The ASCII char `c` looks like and the Unicode name of `c`, if it is easily mistaken for RON syntax
+pub fn confusable(c: char) -> Option<(char, &'static str)> {
+    CONFUSABLES
+        .iter()
+        .find(|(confusable, ..)| *confusable == c)
+        .map(|&(_, looks_like, name)| (looks_like, name))
 }
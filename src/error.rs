@@ -1,15 +1,52 @@
-use std::{
-    fmt::{Display, Formatter},
-    io::stderr,
-};
+use std::fmt::{Display, Formatter};
 
-use crate::location::Location;
+use crate::{
+    files::FileId,
+    location::Location,
+    path::{Path, PathSegment},
+    render::{ColorChoice, Renderer},
+};
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct ErrorContext {
     pub start_end: Option<(Location, Location)>,
+    /// The file in [`Files`] the locations point into
+    pub file_id: Option<FileId>,
+    /// Used if the error is rendered without [`Files`]
     pub file_name: Option<String>,
+    /// Used if the error is rendered without [`Files`]
     pub file_content: Option<String>,
+    /// Secondary locations, printed after the primary one
+    pub labels: Vec<Label>,
+    /// Printed below the source snippet
+    pub notes: Vec<Note>,
+    /// Where in the deserialized value the error occurred
+    pub path: Path,
+}
+
+/// A secondary location of an error, e.g. where a duplicate field was first defined
+#[derive(Clone, Debug, PartialEq)]
+pub struct Label {
+    pub start: Location,
+    pub end: Location,
+    pub message: String,
+    /// The file of the label, `None` for the file of the error
+    pub file_id: Option<FileId>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NoteKind {
+    /// Additional information, e.g. why something is not allowed
+    Note,
+    /// A suggestion how to fix the error
+    Help,
+}
+
+/// A `= note: ..` or `= help: ..` line of an error
+#[derive(Clone, Debug, PartialEq)]
+pub struct Note {
+    pub kind: NoteKind,
+    pub message: String,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -31,6 +68,18 @@ impl Error {
         }
     }
 
+    /// Set the file this error points into, if it is `None`.
+    /// Keeps an already set file.
+    pub fn context_file(self, file_id: FileId) -> Self {
+        let mut context = self.context.unwrap_or_default();
+        context.file_id.get_or_insert(file_id);
+
+        Error {
+            kind: self.kind,
+            context: Some(context),
+        }
+    }
+
     /// Set file name for this error, if they are `None`.
     /// Keeps already set file name.
     pub fn context_file_name(self, file_name: String) -> Self {
@@ -55,6 +104,122 @@ impl Error {
         }
     }
 
+    /// Add a secondary location to this error.
+    pub fn context_label(self, start: Location, end: Location, message: impl Into<String>) -> Self {
+        self.push_label(Label {
+            start,
+            end,
+            message: message.into(),
+            file_id: None,
+        })
+    }
+
+    /// Add a secondary location in another file to this error,
+    /// e.g. the file including the one with the error.
+    pub fn context_label_in(
+        self,
+        file_id: FileId,
+        start: Location,
+        end: Location,
+        message: impl Into<String>,
+    ) -> Self {
+        self.push_label(Label {
+            start,
+            end,
+            message: message.into(),
+            file_id: Some(file_id),
+        })
+    }
+
+    fn push_label(self, label: Label) -> Self {
+        let mut context = self.context.unwrap_or_default();
+        context.labels.push(label);
+
+        Error {
+            kind: self.kind,
+            context: Some(context),
+        }
+    }
+
+    /// Add a `= note: ..` line to this error.
+    pub fn context_note(self, message: impl Into<String>) -> Self {
+        self.push_note(NoteKind::Note, message.into())
+    }
+
+    /// Add a `= help: ..` line to this error.
+    pub fn context_help(self, message: impl Into<String>) -> Self {
+        self.push_note(NoteKind::Help, message.into())
+    }
+
+    fn push_note(self, kind: NoteKind, message: String) -> Self {
+        let mut context = self.context.unwrap_or_default();
+        context.notes.push(Note { kind, message });
+
+        Error {
+            kind: self.kind,
+            context: Some(context),
+        }
+    }
+
+    /// Prepend a segment to the path of this error,
+    /// while it propagates out of the value containing the segment.
+    pub fn context_path(self, segment: PathSegment) -> Self {
+        let mut context = self.context.unwrap_or_default();
+        context.path.push_front(segment);
+
+        Error {
+            kind: self.kind,
+            context: Some(context),
+        }
+    }
+
+    /// The file this error points into
+    pub fn file_id(&self) -> Option<FileId> {
+        self.context.as_ref().and_then(|c| c.file_id)
+    }
+
+    /// Secondary locations of this error
+    pub fn labels(&self) -> &[Label] {
+        self.context
+            .as_ref()
+            .map(|c| c.labels.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Where in the deserialized value this error occurred, `None` for errors at the top level
+    pub fn path(&self) -> Option<&Path> {
+        self.context
+            .as_ref()
+            .map(|c| &c.path)
+            .filter(|path| !path.is_empty())
+    }
+
+    /// Displays the error like its `Display` impl, but with `severity` (e.g. `warning`)
+    /// instead of `error` in front
+    ///
+    /// ```
+    /// use ron_reboot::utf8_parser::ast_from_str;
+    ///
+    /// let e = ast_from_str("[1, 2").unwrap_err();
+    ///
+    /// assert!(e.to_string().starts_with("error at 1:6: "));
+    /// assert!(e.display_with_severity("warning").to_string().starts_with("warning at 1:6: "));
+    /// ```
+    pub fn display_with_severity<'a>(&'a self, severity: &'a str) -> WithSeverity<'a> {
+        WithSeverity {
+            error: self,
+            severity,
+        }
+    }
+
+    /// Notes & help lines of this error
+    pub fn notes(&self) -> &[Note] {
+        self.context
+            .as_ref()
+            .map(|c| c.notes.as_slice())
+            .unwrap_or_default()
+    }
+
     /// Set locations for this error, if they are `None`.
     /// Keeps already set locations.
     pub fn start(&self) -> Option<Location> {
@@ -94,119 +259,57 @@ impl serde::de::Error for Error {
             context: None,
         }
     }
+
+    fn missing_field(field: &'static str) -> Self {
+        Error {
+            kind: ErrorKind::MissingField(field.to_owned()),
+            context: None,
+        }
+    }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        // TODO: any way to do this more elegantly?
-        match self.context.as_ref() {
-            Some(context) => match (
-                context.start_end.as_ref(),
-                context.file_name.as_ref(),
-                context.file_content.as_ref(),
-            ) {
-                (Some((start, _)), _, _) => {
-                    write!(f, "error at {}: {}", start, self.kind)
-                }
-                (_, _, _) => {
-                    write!(f, "error: {}", self.kind)
-                }
-            },
-            None => write!(f, "error: {}", self.kind),
+        Display::fmt(&self.display_with_severity("error"), f)
+    }
+}
+
+/// See [`Error::display_with_severity`]
+pub struct WithSeverity<'a> {
+    error: &'a Error,
+    severity: &'a str,
+}
+
+impl Display for WithSeverity<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.severity)?;
+
+        if let Some(start) = self.error.start() {
+            write!(f, " at {}", start)?;
+        }
+        if let Some(path) = self.error.path() {
+            write!(f, " in `{}`", path)?;
         }
+
+        write!(f, ": {}", self.error.kind)
     }
 }
 
 impl std::error::Error for Error {}
 
+/// Prints the error to stderr, with a snippet of the source if the error contains it.
+///
+/// Errors only contain their source if it was attached with [`Error::context_file_content`];
+/// use [`Files::print_error`] otherwise.
+///
+/// Uses colors if stderr is a terminal, see [`Renderer`] for more options.
 pub fn print_error(e: &Error) -> std::io::Result<()> {
-    use std::io::Write;
-
-    let f = stderr();
-    let mut f = f.lock();
-    match e.context.as_ref() {
-        Some(context) => match (
-            context.start_end.as_ref(),
-            context.file_name.as_ref(),
-            context.file_content.as_ref(),
-        ) {
-            (Some((start, end)), file_name, Some(file_content)) => {
-                let max_line_col_width = start.line.max(end.line).to_string().len();
-                let col_ws_rep = " ".repeat(max_line_col_width);
-                writeln!(f, "error: {}", e.kind)?;
-                writeln!(
-                    f,
-                    "{}--> {}:{}:{}",
-                    col_ws_rep,
-                    file_name.map(AsRef::as_ref).unwrap_or("string"),
-                    start.line,
-                    start.column
-                )?;
-
-                writeln!(f, "{} |", col_ws_rep)?;
-                let mut lines = file_content.lines().skip(start.line as usize - 1);
-                let start_line_string = start.line.to_string();
-                let start_line_padding = " ".repeat(max_line_col_width - start_line_string.len());
-
-                if start.line == end.line {
-                    // The first line
-                    writeln!(
-                        f,
-                        "{}{} | {}",
-                        start_line_padding,
-                        start.line,
-                        lines.next().unwrap_or_default()
-                    )?;
-                    // it's just one line, mark the whole span with ^
-                    writeln!(
-                        f,
-                        "{} | {}{}",
-                        col_ws_rep,
-                        " ".repeat(start.column as usize - 1),
-                        "^".repeat((end.column - start.column) as usize)
-                    )?;
-                } else {
-                    // The first line
-                    writeln!(
-                        f,
-                        "{}{} |   {}",
-                        start_line_padding,
-                        start.line,
-                        lines.next().unwrap_or_default()
-                    )?;
-                    writeln!(
-                        f,
-                        "{} |  {}^",
-                        col_ws_rep,
-                        "_".repeat((start.column - 1) as usize),
-                    )?;
-                    for line_number in start.line + 1..=end.line {
-                        let line_nr_string = line_number.to_string();
-                        let line_padding = " ".repeat(max_line_col_width - line_nr_string.len());
-                        writeln!(
-                            f,
-                            "{}{} | | {}",
-                            line_padding,
-                            line_nr_string,
-                            lines.next().unwrap_or_default()
-                        )?;
-                    }
-
-                    writeln!(
-                        f,
-                        "{} | |{}^",
-                        col_ws_rep,
-                        "_".repeat((end.column - 1) as usize)
-                    )?;
-                }
-
-                writeln!(f, "{} |", col_ws_rep)
-            }
-            (_, Some(file_name), _) => writeln!(f, "file \"{}\": {}", file_name, e),
-            _ => writeln!(f, "{}", e),
-        },
-        _ => writeln!(f, "{}", e),
-    }
+    Renderer::new().color(ColorChoice::Auto).print_error(e)
+}
+
+/// Like [`print_error`], but writes the error to `f` instead of stderr, without colors
+pub fn write_error(f: &mut impl std::io::Write, e: &Error) -> std::io::Result<()> {
+    Renderer::new().write_error(f, e)
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -219,6 +322,26 @@ pub enum ErrorKind {
 
     ParseError(String),
 
+    /// A struct field is defined more than once
+    DuplicateField(String),
+    /// A map key occurs more than once
+    DuplicateKey,
+    /// A required struct field is absent
+    MissingField(String),
+    /// A struct field the target type doesn't have, reported as a warning
+    IgnoredField(String),
+    /// A value the target type skipped, reported as a warning
+    IgnoredValue,
+
+    /// Containers are nested deeper than the configured limit
+    RecursionLimitExceeded(usize),
+
+    /// A lint diagnostic, see [`crate::lint`]
+    Lint {
+        rule: &'static str,
+        message: String,
+    },
+
     IoError(String),
     Custom(String),
 }
@@ -233,6 +356,15 @@ impl Display for ErrorKind {
             ErrorKind::ExpectedString => write!(f, "expected string"),
             ErrorKind::ExpectedList => write!(f, "expected list"),
             ErrorKind::ParseError(e) => write!(f, "parsing error: {}", e),
+            ErrorKind::DuplicateField(name) => write!(f, "duplicate field `{}`", name),
+            ErrorKind::DuplicateKey => write!(f, "duplicate map key"),
+            ErrorKind::MissingField(name) => write!(f, "missing field `{}`", name),
+            ErrorKind::IgnoredField(name) => write!(f, "ignored field `{}`", name),
+            ErrorKind::IgnoredValue => write!(f, "ignored value"),
+            ErrorKind::RecursionLimitExceeded(limit) => {
+                write!(f, "exceeded recursion limit of {}", limit)
+            }
+            ErrorKind::Lint { rule, message } => write!(f, "{} [{}]", message, rule),
             ErrorKind::IoError(e) => write!(f, "io error: {}", e),
             ErrorKind::Custom(s) => write!(f, "{}", s),
         }
@@ -0,0 +1,88 @@
+use std::io::Write;
+
+use crate::{ColorChoice, Error, Renderer};
+
+/// Identifies a file added to [`Files`]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FileId(usize);
+
+#[derive(Clone, Debug)]
+struct File {
+    name: String,
+    source: String,
+}
+
+/// Names & sources of RON files, referenced by errors through [`FileId`]s
+///
+/// Errors only store a [`FileId`] and locations; the sources are looked up when rendering,
+/// so errors stay cheap to create and can point into several files.
+///
+/// ```
+/// use ron_reboot::{utf8_parser::ast_from_str, Files};
+///
+/// let mut files = Files::new();
+/// let id = files.add("config.ron", "(width: 800, height: 600");
+///
+/// let e = ast_from_str(files.source(id)).unwrap_err().context_file(id);
+///
+/// let mut rendered = vec![];
+/// files.write_error(&mut rendered, &e).unwrap();
+/// let rendered = String::from_utf8(rendered).unwrap();
+///
+/// assert!(rendered.contains("--> config.ron:1:"));
+/// assert!(rendered.contains("1 | (width: 800, height: 600"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Files {
+    files: Vec<File>,
+}
+
+impl Files {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file, returning the id to attach to its errors with [`Error::context_file`]
+    pub fn add(&mut self, name: impl Into<String>, source: impl Into<String>) -> FileId {
+        self.files.push(File {
+            name: name.into(),
+            source: source.into(),
+        });
+
+        FileId(self.files.len() - 1)
+    }
+
+    /// # Panics
+    ///
+    /// If `id` was created by another `Files`
+    pub fn name(&self, id: FileId) -> &str {
+        &self.files[id.0].name
+    }
+
+    /// # Panics
+    ///
+    /// If `id` was created by another `Files`
+    pub fn source(&self, id: FileId) -> &str {
+        &self.files[id.0].source
+    }
+
+    /// Name and source of `id`, `None` if it was created by another `Files`
+    pub fn get(&self, id: FileId) -> Option<(&str, &str)> {
+        self.files
+            .get(id.0)
+            .map(|file| (file.name.as_str(), file.source.as_str()))
+    }
+
+    /// Like [`crate::print_error`], but looks up the sources of the error and its labels
+    pub fn print_error(&self, e: &Error) -> std::io::Result<()> {
+        Renderer::new()
+            .color(ColorChoice::Auto)
+            .files(self)
+            .print_error(e)
+    }
+
+    /// Like [`crate::write_error`], but looks up the sources of the error and its labels
+    pub fn write_error(&self, f: &mut impl Write, e: &Error) -> std::io::Result<()> {
+        Renderer::new().files(self).write_error(f, e)
+    }
+}
@@ -3,15 +3,28 @@ pub use self::utf8_parser::serde::from_str as from_str_serde;
 #[cfg(feature = "value")]
 pub use self::value::Value;
 pub use self::{
-    error::{print_error, Error},
+    ast::Spanned,
+    error::{print_error, write_error, Error, Label, Note, NoteKind, WithSeverity},
+    files::{FileId, Files},
     location::Location,
+    path::{Path, PathSegment},
+    render::{ColorChoice, Renderer},
 };
 
-mod ast;
+pub mod ast;
+/// Tables for fast lookup of char categories
+#[cfg(any(feature = "utf8_parser", feature = "value"))]
+mod char_categories;
 mod error;
+mod files;
+pub mod lint;
 mod location;
+mod path;
+mod render;
+#[cfg(feature = "serde")]
+mod spanned;
 #[cfg(feature = "utf8_parser")]
 pub mod utf8_parser;
 mod util;
 #[cfg(feature = "value")]
-mod value;
+pub mod value;
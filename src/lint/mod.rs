@@ -0,0 +1,209 @@
+//! Lints for RON documents
+//!
+//! Lints catch mistakes which are valid RON, but most likely not what the author intended,
+//! e.g. a struct field which is defined twice.
+//!
+//! ```
+//! use ron_reboot::{
+//!     lint::{lint, Level, LintConfig, Rule},
+//!     utf8_parser::ast_from_str,
+//! };
+//!
+//! let ron = ast_from_str("(a: 1, a: 2)").unwrap();
+//!
+//! let diagnostics = lint(&ron, &LintConfig::default());
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].rule, Rule::DuplicateFields);
+//! assert_eq!(diagnostics[0].level, Level::Deny);
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+pub use self::rules::Rule;
+use crate::{ast, error::ErrorKind, ColorChoice, Error, Files, Label, Location, Renderer};
+
+/// Lint rule implementations
+mod rules;
+#[cfg(all(test, feature = "utf8_parser"))]
+mod tests;
+
+/// How a violation of a [`Rule`] is reported
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    /// The rule is not checked
+    Allow,
+    /// Violations are reported as warnings
+    Warn,
+    /// Violations are reported as errors
+    Deny,
+}
+
+impl Level {
+    /// The severity printed in front of diagnostics with this level
+    pub fn severity(self) -> &'static str {
+        match self {
+            Level::Allow => "allow",
+            Level::Warn => "warning",
+            Level::Deny => "error",
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Level::Allow),
+            "warn" => Ok(Level::Warn),
+            "deny" => Ok(Level::Deny),
+            _ => Err(format!(
+                "unknown lint level `{}`, expected `allow`, `warn` or `deny`",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Level::Allow => write!(f, "allow"),
+            Level::Warn => write!(f, "warn"),
+            Level::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+/// Configures the [`Level`] of each [`Rule`].
+///
+/// Rules which are not configured explicitly use [`Rule::default_level`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LintConfig {
+    levels: BTreeMap<Rule, Level>,
+}
+
+impl LintConfig {
+    /// Returns the level `rule` is reported with
+    pub fn level(&self, rule: Rule) -> Level {
+        self.levels
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_level())
+    }
+
+    /// Overrides the level of `rule`
+    pub fn set_level(&mut self, rule: Rule, level: Level) {
+        self.levels.insert(rule, level);
+    }
+
+    /// Overrides the level of `rule`, returning `self`
+    pub fn with_level(mut self, rule: Rule, level: Level) -> Self {
+        self.set_level(rule, level);
+
+        self
+    }
+}
+
+/// A located rule violation
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub rule: Rule,
+    pub level: Level,
+    /// Message & location of the violation, with [`ErrorKind::Lint`] as kind
+    pub error: Error,
+}
+
+impl Diagnostic {
+    /// Returns `true` if this diagnostic should be treated as an error
+    pub fn is_error(&self) -> bool {
+        self.level == Level::Deny
+    }
+
+    /// Prints this diagnostic in the same format as [`crate::print_error`]
+    pub fn print(&self) -> std::io::Result<()> {
+        self.print_with(&Renderer::new().color(ColorChoice::Auto))
+    }
+
+    /// Prints this diagnostic in the same format as [`Files::print_error`]
+    pub fn print_with_files(&self, files: &Files) -> std::io::Result<()> {
+        self.print_with(&Renderer::new().color(ColorChoice::Auto).files(files))
+    }
+
+    /// Prints this diagnostic to stderr with a custom [`Renderer`]
+    pub fn print_with(&self, renderer: &Renderer) -> std::io::Result<()> {
+        renderer.print_with_severity(self.level.severity(), &self.error)
+    }
+}
+
+/// Collects diagnostics for the rules which are not allowed
+pub(crate) struct Context<'c> {
+    config: &'c LintConfig,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'c> Context<'c> {
+    pub fn is_enabled(&self, rule: Rule) -> bool {
+        self.config.level(rule) != Level::Allow
+    }
+
+    pub fn emit(&mut self, rule: Rule, start: Location, end: Location, message: String) {
+        self.emit_with_labels(rule, start, end, message, vec![]);
+    }
+
+    pub fn emit_with_labels(
+        &mut self,
+        rule: Rule,
+        start: Location,
+        end: Location,
+        message: String,
+        labels: Vec<Label>,
+    ) {
+        let level = self.config.level(rule);
+        if level == Level::Allow {
+            return;
+        }
+
+        let error = Error {
+            kind: ErrorKind::Lint {
+                rule: rule.name(),
+                message,
+            },
+            context: None,
+        }
+        .context_loc(start, end);
+
+        self.diagnostics.push(Diagnostic {
+            rule,
+            level,
+            error: labels.into_iter().fold(error, |error, label| {
+                error.context_label(label.start, label.end, label.message)
+            }),
+        });
+    }
+}
+
+/// Checks `ron` against all rules that are not allowed by `config`.
+///
+/// The returned diagnostics are ordered by their location.
+pub fn lint(ron: &ast::Ron, config: &LintConfig) -> Vec<Diagnostic> {
+    let mut context = Context {
+        config,
+        diagnostics: vec![],
+    };
+
+    for &rule in Rule::ALL {
+        if context.is_enabled(rule) {
+            rules::check(rule, ron, &mut context);
+        }
+    }
+
+    let mut diagnostics = context.diagnostics;
+    diagnostics.sort_by_key(|d| d.error.start());
+
+    diagnostics
+}
@@ -0,0 +1,337 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::{
+    ast::{Attribute, Expr, Extension, Integer, Ron, Spanned, Struct, Tagged, Untagged},
+    lint::{Context, Level},
+    Label,
+};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum Rule {
+    /// A struct defines the same field more than once
+    DuplicateFields,
+    /// A map contains the same key more than once
+    DuplicateMapKeys,
+    /// An integer is used where floats are used elsewhere (same list or same struct field)
+    MixedNumberTypes,
+    /// An extension is enabled that has no effect.
+    ///
+    /// This is the case for extensions which are enabled more than once,
+    /// `implicit_outer_struct` when the outer struct has parentheses and
+    /// `unquoted_map_keys` when no map key is unquoted. Whether `implicit_some`
+    /// and `unwrap_newtypes` are used depends on the deserialized type.
+    UnusedExtensions,
+    /// Structs without a name are mixed with named structs in the same list or map
+    MixedStructStyles,
+}
+
+impl Rule {
+    /// All rules, in the order they are checked
+    pub const ALL: &'static [Rule] = &[
+        Rule::DuplicateFields,
+        Rule::DuplicateMapKeys,
+        Rule::MixedNumberTypes,
+        Rule::UnusedExtensions,
+        Rule::MixedStructStyles,
+    ];
+
+    /// The `snake_case` name used in configs and diagnostics
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::DuplicateFields => "duplicate_fields",
+            Rule::DuplicateMapKeys => "duplicate_map_keys",
+            Rule::MixedNumberTypes => "mixed_number_types",
+            Rule::UnusedExtensions => "unused_extensions",
+            Rule::MixedStructStyles => "mixed_struct_styles",
+        }
+    }
+
+    /// The level used unless the [`LintConfig`](super::LintConfig) overrides it
+    pub fn default_level(self) -> Level {
+        match self {
+            Rule::DuplicateFields | Rule::DuplicateMapKeys => Level::Deny,
+            Rule::MixedNumberTypes | Rule::UnusedExtensions | Rule::MixedStructStyles => {
+                Level::Warn
+            }
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    /// Parses the rule name, accepting both `snake_case` and `kebab-case`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.replace('-', "_");
+
+        Rule::ALL
+            .iter()
+            .copied()
+            .find(|rule| rule.name() == name)
+            .ok_or_else(|| format!("unknown lint rule `{}`", s))
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+pub(super) fn check(rule: Rule, ron: &Ron, context: &mut Context) {
+    match rule {
+        Rule::DuplicateFields => walk(&ron.expr, &mut |expr| duplicate_fields(expr, context)),
+        Rule::DuplicateMapKeys => walk(&ron.expr, &mut |expr| duplicate_map_keys(expr, context)),
+        Rule::MixedNumberTypes => mixed_number_types(ron, context),
+        Rule::UnusedExtensions => unused_extensions(ron, context),
+        Rule::MixedStructStyles => walk(&ron.expr, &mut |expr| mixed_struct_styles(expr, context)),
+    }
+}
+
+/// Calls `f` for `expr` and all expressions nested in it (pre-order)
+fn walk<'b, 'a>(expr: &'b Spanned<Expr<'a>>, f: &mut impl FnMut(&'b Spanned<Expr<'a>>)) {
+    f(expr);
+
+    match &expr.value {
+        Expr::Optional(Some(inner)) => walk(inner, f),
+        Expr::Tagged(Tagged { untagged, .. }) => match &untagged.value {
+            Untagged::Struct(s) => s.fields.iter().for_each(|kv| walk(&kv.value.value, f)),
+            Untagged::Tuple(t) => t.elements.iter().for_each(|e| walk(e, f)),
+            Untagged::Unit => {}
+        },
+        Expr::Tuple(t) => t.elements.iter().for_each(|e| walk(e, f)),
+        Expr::List(l) => l.elements.iter().for_each(|e| walk(e, f)),
+        Expr::Map(m) => m.entries.iter().for_each(|kv| {
+            walk(&kv.value.key, f);
+            walk(&kv.value.value, f);
+        }),
+        Expr::Struct(s) => s.fields.iter().for_each(|kv| walk(&kv.value.value, f)),
+        Expr::Unit
+        | Expr::Optional(None)
+        | Expr::Bool(_)
+        | Expr::Integer(_)
+        | Expr::Str(_)
+        | Expr::String(_)
+        | Expr::Char(_)
+        | Expr::Decimal(_)
+        | Expr::NonFinite(_)
+        | Expr::UnquotedKey(_) => {}
+    }
+}
+
+/// Returns the struct name (if any) and the struct, for both `(a: 1)` and `Name(a: 1)`
+fn as_struct<'b, 'a>(expr: &'b Expr<'a>) -> Option<(Option<&'a str>, &'b Struct<'a>)> {
+    match expr {
+        Expr::Struct(s) => Some((None, s)),
+        Expr::Tagged(Tagged {
+            ident,
+            untagged:
+                Spanned {
+                    value: Untagged::Struct(s),
+                    ..
+                },
+        }) => Some((Some(ident.value.0), s)),
+        _ => None,
+    }
+}
+
+fn integer_to_decimal_string(i: &Integer) -> String {
+    match i {
+        Integer::Signed(s) => format!(
+            "{}{}.0",
+            if s.sign.into_i8() < 0 { "-" } else { "+" },
+            s.number
+        ),
+        Integer::Unsigned(u) => format!("{}.0", u.number),
+    }
+}
+
+fn first_defined_here<T>(first: &Spanned<T>) -> Label {
+    Label {
+        start: first.start,
+        end: first.end,
+        message: "first defined here".to_owned(),
+        file_id: None,
+    }
+}
+
+fn duplicate_fields(expr: &Spanned<Expr>, context: &mut Context) {
+    if let Some((_, s)) = as_struct(&expr.value) {
+        for (first, duplicate) in s.duplicate_fields() {
+            context.emit_with_labels(
+                Rule::DuplicateFields,
+                duplicate.start,
+                duplicate.end,
+                format!("duplicate field `{}`", duplicate.value.0),
+                vec![first_defined_here(first)],
+            );
+        }
+    }
+}
+
+fn duplicate_map_keys(expr: &Spanned<Expr>, context: &mut Context) {
+    if let Expr::Map(m) = &expr.value {
+        for (first, duplicate) in m.duplicate_keys() {
+            context.emit_with_labels(
+                Rule::DuplicateMapKeys,
+                duplicate.start,
+                duplicate.end,
+                "duplicate map key".to_owned(),
+                vec![first_defined_here(first)],
+            );
+        }
+    }
+}
+
+fn mixed_number_types(ron: &Ron, context: &mut Context) {
+    // Lists with both integers & decimals
+    walk(&ron.expr, &mut |expr| {
+        if let Expr::List(l) = &expr.value {
+            let any_decimal = l
+                .elements
+                .iter()
+                .any(|e| matches!(e.value, Expr::Decimal(_) | Expr::NonFinite(_)));
+            if !any_decimal {
+                return;
+            }
+
+            for element in &l.elements {
+                if let Expr::Integer(i) = &element.value {
+                    context.emit(
+                        Rule::MixedNumberTypes,
+                        element.start,
+                        element.end,
+                        format!(
+                            "integer in a list of floats, consider writing `{}`",
+                            integer_to_decimal_string(i)
+                        ),
+                    );
+                }
+            }
+        }
+    });
+
+    // Struct fields which are a decimal somewhere else, keyed by struct name & field name
+    let mut decimal_fields = HashMap::new();
+    walk(&ron.expr, &mut |expr| {
+        if let Some((name, s)) = as_struct(&expr.value) {
+            for field in &s.fields {
+                if let Expr::Decimal(_) | Expr::NonFinite(_) = field.value.value.value {
+                    decimal_fields
+                        .entry((name, field.value.key.value.0))
+                        .or_insert(field.value.value.start);
+                }
+            }
+        }
+    });
+    walk(&ron.expr, &mut |expr| {
+        if let Some((name, s)) = as_struct(&expr.value) {
+            for field in &s.fields {
+                let key = field.value.key.value.0;
+                let value = &field.value.value;
+                if let (Expr::Integer(i), Some(decimal_loc)) =
+                    (&value.value, decimal_fields.get(&(name, key)))
+                {
+                    context.emit(
+                        Rule::MixedNumberTypes,
+                        value.start,
+                        value.end,
+                        format!(
+                            "field `{}` is a float at {}, consider writing `{}`",
+                            key,
+                            decimal_loc,
+                            integer_to_decimal_string(i)
+                        ),
+                    );
+                }
+            }
+        }
+    });
+}
+
+fn unused_extensions(ron: &Ron, context: &mut Context) {
+    let mut enabled = Vec::new();
+
+    for attribute in &ron.attributes {
+        match &attribute.value {
+            Attribute::Enable(extensions) => {
+                for extension in &extensions.value {
+                    match enabled.iter().find(|(e, _)| *e == &extension.value) {
+                        Some((_, first)) => context.emit(
+                            Rule::UnusedExtensions,
+                            extension.start,
+                            extension.end,
+                            format!(
+                                "extension `{}` is already enabled at {}",
+                                extension.value.name(),
+                                first
+                            ),
+                        ),
+                        None if !has_effect(&extension.value, ron) => context.emit(
+                            Rule::UnusedExtensions,
+                            extension.start,
+                            extension.end,
+                            format!("extension `{}` has no effect", extension.value.name()),
+                        ),
+                        None => enabled.push((&extension.value, extension.start)),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether enabling `extension` changes how `ron` is read, as far as this is known without types
+fn has_effect(extension: &Extension, ron: &Ron) -> bool {
+    match extension {
+        // The implicit outer struct starts with its first field instead of `(`
+        Extension::ImplicitOuterStruct => match &ron.expr.value {
+            Expr::Struct(s) => matches!(s.fields.first(), Some(f) if f.start == ron.expr.start),
+            _ => false,
+        },
+        Extension::UnquotedMapKeys => {
+            let mut unquoted = false;
+            walk(&ron.expr, &mut |expr| {
+                unquoted |= matches!(expr.value, Expr::UnquotedKey(_))
+            });
+            unquoted
+        }
+        Extension::UnwrapNewtypes | Extension::ImplicitSome => true,
+    }
+}
+
+fn mixed_struct_styles(expr: &Spanned<Expr>, context: &mut Context) {
+    let siblings: Vec<&Spanned<Expr>> = match &expr.value {
+        Expr::List(l) => l.elements.iter().collect(),
+        Expr::Map(m) => m.entries.iter().map(|kv| &kv.value.value).collect(),
+        _ => return,
+    };
+
+    let first_name = siblings
+        .iter()
+        .filter_map(|e| as_struct(&e.value))
+        .find_map(|(name, _)| name);
+    let first_name = match first_name {
+        Some(name) => name,
+        None => return,
+    };
+
+    for sibling in siblings {
+        if let Some((None, _)) = as_struct(&sibling.value) {
+            context.emit(
+                Rule::MixedStructStyles,
+                sibling.start,
+                sibling.end,
+                format!(
+                    "struct without a name next to named structs, consider writing `{}(..)`",
+                    first_name
+                ),
+            );
+        }
+    }
+}
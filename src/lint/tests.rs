@@ -0,0 +1,191 @@
+use crate::{
+    lint::{lint, Diagnostic, Level, LintConfig, Rule},
+    utf8_parser::ast_from_str,
+    Location,
+};
+
+fn lint_str(s: &str) -> Vec<Diagnostic> {
+    lint(&ast_from_str(s).unwrap(), &LintConfig::default())
+}
+
+fn rules(diagnostics: &[Diagnostic]) -> Vec<Rule> {
+    diagnostics.iter().map(|d| d.rule).collect()
+}
+
+#[test]
+fn clean_document() {
+    assert_eq!(
+        lint_str(
+            r#"
+#![enable(implicit_some)]
+
+Config(
+    names: ["a", "b"],
+    scale: [1.0, 2.5],
+    points: [Point(x: 1, y: 2), Point(x: 3, y: 4)],
+)"#
+        ),
+        vec![]
+    );
+}
+
+#[test]
+fn duplicate_fields() {
+    let diagnostics = lint_str("Point(x: 1, y: 2, x: 3)");
+
+    assert_eq!(rules(&diagnostics), vec![Rule::DuplicateFields]);
+    assert_eq!(diagnostics[0].level, Level::Deny);
+    assert_eq!(
+        diagnostics[0].error.start(),
+        Some(Location {
+            line: 1,
+            column: 19
+        })
+    );
+    assert_eq!(
+        diagnostics[0].error.kind.to_string(),
+        "duplicate field `x` [duplicate_fields]"
+    );
+    assert_eq!(
+        diagnostics[0].error.labels()[0].start,
+        Location { line: 1, column: 7 }
+    );
+}
+
+#[test]
+fn duplicate_fields_nested() {
+    assert_eq!(
+        rules(&lint_str("[(a: 1), Some((b: 1, b: 2))]")),
+        vec![Rule::DuplicateFields]
+    );
+}
+
+#[test]
+fn duplicate_map_keys() {
+    let diagnostics = lint_str(r#"{ "a": 1, "b": 2, "a": 3 }"#);
+
+    assert_eq!(rules(&diagnostics), vec![Rule::DuplicateMapKeys]);
+    assert_eq!(
+        diagnostics[0].error.start(),
+        Some(Location {
+            line: 1,
+            column: 19
+        })
+    );
+
+    let diagnostics = lint_str(r#"{ "a": 1, "\u{61}": 2, 1: 3, +1: 4, 1.0: 5, 1.00: 6 }"#);
+    assert_eq!(rules(&diagnostics), vec![Rule::DuplicateMapKeys; 3]);
+
+    let diagnostics = lint_str("#![enable(unquoted_map_keys)]\n{ a: 1, \"a\": 2 }");
+    assert_eq!(rules(&diagnostics), vec![Rule::DuplicateMapKeys]);
+
+    let diagnostics = lint_str(
+        r#"{ (1, "a"): 1, [+1, "\u{61}"]: 2, 0.0: 3, -0.0: 4, P(x: 1): 5, P(x: 2): 6, P(x: 1): 7 }"#,
+    );
+    assert_eq!(rules(&diagnostics), vec![Rule::DuplicateMapKeys; 3]);
+}
+
+#[test]
+fn mixed_number_types_list() {
+    let diagnostics = lint_str("[1.0, 2, -3.5, +4]");
+
+    assert_eq!(
+        rules(&diagnostics),
+        vec![Rule::MixedNumberTypes, Rule::MixedNumberTypes]
+    );
+    assert_eq!(
+        diagnostics[1].error.kind.to_string(),
+        "integer in a list of floats, consider writing `+4.0` [mixed_number_types]"
+    );
+}
+
+#[test]
+fn mixed_number_types_fields() {
+    let diagnostics = lint_str("[Point(x: 1.5, y: 2), Point(x: 3, y: 4), Size(x: 1)]");
+
+    assert_eq!(rules(&diagnostics), vec![Rule::MixedNumberTypes]);
+    assert_eq!(
+        diagnostics[0].error.start(),
+        Some(Location {
+            line: 1,
+            column: 32
+        })
+    );
+}
+
+#[test]
+fn unused_extensions() {
+    let diagnostics = lint_str(
+        "#![enable(implicit_some)]
+#![enable(unwrap_newtypes, implicit_some)]
+()",
+    );
+
+    assert_eq!(rules(&diagnostics), vec![Rule::UnusedExtensions]);
+    assert_eq!(
+        diagnostics[0].error.start(),
+        Some(Location {
+            line: 2,
+            column: 28
+        })
+    );
+}
+
+#[test]
+fn extensions_without_effect() {
+    let diagnostics = lint_str(
+        "#![enable(implicit_outer_struct, unquoted_map_keys)]
+(names: {\"a\": 1})",
+    );
+
+    assert_eq!(
+        rules(&diagnostics),
+        vec![Rule::UnusedExtensions, Rule::UnusedExtensions]
+    );
+    assert_eq!(
+        diagnostics[1].error.start(),
+        Some(Location {
+            line: 1,
+            column: 34
+        })
+    );
+
+    assert_eq!(
+        lint_str(
+            "#![enable(implicit_outer_struct, unquoted_map_keys)]
+names: {a: 1}"
+        ),
+        vec![]
+    );
+}
+
+#[test]
+fn mixed_struct_styles() {
+    let diagnostics = lint_str("[Point(x: 1, y: 2), (x: 3, y: 4)]");
+
+    assert_eq!(rules(&diagnostics), vec![Rule::MixedStructStyles]);
+    assert_eq!(diagnostics[0].level, Level::Warn);
+}
+
+#[test]
+fn configured_levels() {
+    let ron = ast_from_str("[Point(x: 1, y: 2), (x: 3, y: 4, x: 5)]").unwrap();
+    let config = LintConfig::default()
+        .with_level(Rule::MixedStructStyles, Level::Allow)
+        .with_level(Rule::DuplicateFields, Level::Warn);
+
+    let diagnostics = lint(&ron, &config);
+
+    assert_eq!(rules(&diagnostics), vec![Rule::DuplicateFields]);
+    assert_eq!(diagnostics[0].level, Level::Warn);
+    assert!(!diagnostics[0].is_error());
+}
+
+#[test]
+fn rule_names() {
+    for &rule in Rule::ALL {
+        assert_eq!(rule.name().parse(), Ok(rule));
+    }
+    assert_eq!("duplicate-map-keys".parse(), Ok(Rule::DuplicateMapKeys));
+    assert!("no_such_rule".parse::<Rule>().is_err());
+}
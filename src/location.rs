@@ -10,6 +10,23 @@ pub struct Location {
     pub column: u32,
 }
 
+#[cfg(feature = "utf8_parser_serde1")]
+impl Location {
+    /// The location after `text`, which starts at `self`
+    pub(crate) fn after(self, text: &str) -> Location {
+        match text.rfind('\n') {
+            Some(last) => Location {
+                line: self.line + text.matches('\n').count() as u32,
+                column: text[last + 1..].chars().count() as u32 + 1,
+            },
+            None => Location {
+                column: self.column + text.chars().count() as u32,
+                ..self
+            },
+        }
+    }
+}
+
 impl Display for Location {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}:{}", self.line, self.column)
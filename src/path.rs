@@ -0,0 +1,64 @@
+use std::fmt::{Display, Formatter};
+
+/// One step from a value into a nested value
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PathSegment {
+    /// A struct field, `.name`
+    Field(String),
+    /// A map entry, `[key]` with the key as RON (e.g. `"a"`)
+    Key(String),
+    /// A list or tuple element, `[3]`
+    Index(usize),
+    /// The content of an enum variant, `.Variant`
+    Variant(String),
+}
+
+/// Where in the deserialized value an error occurred, e.g. `mapping["a"].flags[3].Struct.optional`
+///
+/// ```
+/// use ron_reboot::{from_str_serde, PathSegment};
+/// use std::collections::HashMap;
+///
+/// let e = from_str_serde::<HashMap<String, Vec<u8>>>(r#"{ "a": [1, -2] }"#).unwrap_err();
+/// let path = e.path().unwrap();
+///
+/// assert_eq!(path.to_string(), r#"["a"][1]"#);
+/// assert_eq!(path.segments()[1], PathSegment::Index(1));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Path {
+    segments: Vec<PathSegment>,
+}
+
+impl Path {
+    /// The segments, outermost first
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Paths are built while errors propagate out of nested values, innermost segment first
+    pub(crate) fn push_front(&mut self, segment: PathSegment) {
+        self.segments.insert(0, segment);
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Field(name) | PathSegment::Variant(name) if i == 0 => {
+                    write!(f, "{}", name)?
+                }
+                PathSegment::Field(name) | PathSegment::Variant(name) => write!(f, ".{}", name)?,
+                PathSegment::Key(key) => write!(f, "[{}]", key)?,
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,402 @@
+use std::{
+    env,
+    fmt::{self, Display, Write},
+    io::{stderr, IsTerminal},
+};
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::{
+    error::{ErrorContext, NoteKind},
+    Error, Files, Location,
+};
+
+/// When [`Renderer`] uses ANSI colors
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColorChoice {
+    /// Colors if stderr is a terminal, `NO_COLOR` is not set and `TERM` is not `dumb`
+    Auto,
+    Always,
+    #[default]
+    Never,
+}
+
+impl ColorChoice {
+    fn use_color(self) -> bool {
+        match self {
+            ColorChoice::Auto => {
+                env::var_os("NO_COLOR").is_none()
+                    && env::var("TERM").map_or(true, |term| term != "dumb")
+                    && stderr().is_terminal()
+            }
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const BLUE: &str = "\x1b[1;34m";
+
+/// Renders errors in a rustc-like layout, with a snippet of the source
+///
+/// ```
+/// use ron_reboot::{utf8_parser::ast_from_str, ColorChoice, Files, Renderer};
+///
+/// let mut files = Files::new();
+/// let id = files.add("config.ron", "(name:\t\"フェリス\", age: 7 8)");
+///
+/// let e = ast_from_str(files.source(id))
+///     .unwrap_err()
+///     .context_file(id)
+///     .context_help("separate the elements with `,`");
+///
+/// let mut rendered = String::new();
+/// Renderer::new()
+///     .files(&files)
+///     .color(ColorChoice::Never)
+///     .tab_width(2)
+///     .fmt_error(&mut rendered, &e)
+///     .unwrap();
+///
+/// assert!(rendered.ends_with(
+///     "1 | (name:  \"フェリス\", age: 7 8)
+///   |                            ^
+///   |
+///   = help: separate the elements with `,`
+/// "
+/// ));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Renderer<'f> {
+    color: ColorChoice,
+    tab_width: usize,
+    files: Option<&'f Files>,
+}
+
+impl Default for Renderer<'_> {
+    fn default() -> Self {
+        Renderer {
+            color: ColorChoice::Never,
+            tab_width: 4,
+            files: None,
+        }
+    }
+}
+
+impl<'f> Renderer<'f> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Defaults to [`ColorChoice::Never`]
+    pub fn color(self, color: ColorChoice) -> Self {
+        Renderer { color, ..self }
+    }
+
+    /// Number of columns a tab is expanded to, defaults to 4
+    pub fn tab_width(self, tab_width: usize) -> Self {
+        Renderer { tab_width, ..self }
+    }
+
+    /// Looks up the sources of errors with a file id in `files`
+    pub fn files(self, files: &'f Files) -> Self {
+        Renderer {
+            files: Some(files),
+            ..self
+        }
+    }
+
+    /// Prints the error to stderr
+    pub fn print_error(&self, e: &Error) -> std::io::Result<()> {
+        self.print_with_severity("error", e)
+    }
+
+    /// Like [`Renderer::print_error`], but with a custom severity (e.g. `warning`) in the header
+    pub fn print_with_severity(&self, severity: &str, e: &Error) -> std::io::Result<()> {
+        self.write_with_severity(&mut stderr().lock(), severity, e)
+    }
+
+    pub fn write_error(&self, w: &mut impl std::io::Write, e: &Error) -> std::io::Result<()> {
+        self.write_with_severity(w, "error", e)
+    }
+
+    pub fn write_with_severity(
+        &self,
+        w: &mut impl std::io::Write,
+        severity: &str,
+        e: &Error,
+    ) -> std::io::Result<()> {
+        let mut rendered = String::new();
+        // Writing to a `String` can't fail
+        let _ = self.fmt_with_severity(&mut rendered, severity, e);
+
+        w.write_all(rendered.as_bytes())
+    }
+
+    pub fn fmt_error(&self, f: &mut impl Write, e: &Error) -> fmt::Result {
+        self.fmt_with_severity(f, "error", e)
+    }
+
+    pub fn fmt_with_severity(&self, f: &mut impl Write, severity: &str, e: &Error) -> fmt::Result {
+        Painter {
+            renderer: self,
+            color: self.color.use_color(),
+            primary: match severity {
+                "error" => "\x1b[1;31m",
+                "warning" => "\x1b[1;33m",
+                _ => BOLD,
+            },
+        }
+        .error(f, severity, e)
+    }
+
+    /// Name & content of the file the error points into
+    fn source<'s>(&self, context: &'s ErrorContext) -> (Option<&'s str>, Option<&'s str>)
+    where
+        'f: 's,
+    {
+        match (context.file_id, self.files) {
+            (Some(id), Some(files)) => files.get(id).unzip(),
+            _ => (
+                context.file_name.as_deref(),
+                context.file_content.as_deref(),
+            ),
+        }
+    }
+
+    fn char_width(&self, c: char) -> usize {
+        match c {
+            '\t' => self.tab_width,
+            c => c.width().unwrap_or(0),
+        }
+    }
+
+    /// Display width of the chars in front of `column`
+    ///
+    /// Columns past the end of the line (e.g. at EOF) count as one column each.
+    fn width_until(&self, line: &str, column: u32) -> usize {
+        let columns = column.saturating_sub(1) as usize;
+        let chars = line.chars().count();
+
+        line.chars()
+            .take(columns)
+            .map(|c| self.char_width(c))
+            .sum::<usize>()
+            + columns.saturating_sub(chars)
+    }
+
+    /// Display width of the chars from `start` to `end`, at least 1
+    fn width_between(&self, line: &str, start: u32, end: u32) -> usize {
+        self.width_until(line, end)
+            .saturating_sub(self.width_until(line, start))
+            .max(1)
+    }
+
+    fn expand_tabs(&self, line: &str) -> String {
+        line.replace('\t', &" ".repeat(self.tab_width))
+    }
+}
+
+/// Line `n` (1-based) of `source`, empty if there is no such line
+fn source_line(source: &str, n: u32) -> &str {
+    match n.checked_sub(1) {
+        Some(index) => source.lines().nth(index as usize).unwrap_or_default(),
+        None => "",
+    }
+}
+
+struct Painter<'r, 'f> {
+    renderer: &'r Renderer<'f>,
+    color: bool,
+    /// Style of the severity and the primary marker
+    primary: &'static str,
+}
+
+impl Painter<'_, '_> {
+    fn paint(&self, f: &mut impl Write, style: &str, text: impl Display) -> fmt::Result {
+        match self.color {
+            true => write!(f, "{}{}{}", style, text, RESET),
+            false => write!(f, "{}", text),
+        }
+    }
+
+    /// `{line number} | `, the line number is left out for `None`
+    fn gutter(
+        &self,
+        f: &mut impl Write,
+        width: usize,
+        line: Option<u32>,
+        sep: &str,
+    ) -> fmt::Result {
+        let line = line.map(|l| l.to_string()).unwrap_or_default();
+
+        self.paint(
+            f,
+            BLUE,
+            format_args!("{:>width$} {}", line, sep, width = width),
+        )
+    }
+
+    fn error(&self, f: &mut impl Write, severity: &str, e: &Error) -> fmt::Result {
+        let r = self.renderer;
+        let context = match e.context.as_deref() {
+            Some(context) => context,
+            None => return writeln!(f, "{}", e.display_with_severity(severity)),
+        };
+
+        let (file_name, file_content) = r.source(context);
+        let ((start, end), file_content) = match (context.start_end, file_content) {
+            (Some(start_end), Some(file_content)) => (start_end, file_content),
+            _ => {
+                match file_name {
+                    Some(file_name) => writeln!(
+                        f,
+                        "file \"{}\": {}",
+                        file_name,
+                        e.display_with_severity(severity)
+                    )?,
+                    None => writeln!(f, "{}", e.display_with_severity(severity))?,
+                }
+
+                return self.notes(f, 0, context);
+            }
+        };
+
+        let width = context
+            .labels
+            .iter()
+            .map(|label| label.start.line)
+            .fold(start.line.max(end.line), u32::max)
+            .to_string()
+            .len();
+        let line = |n: u32| source_line(file_content, n);
+
+        self.paint(f, self.primary, severity)?;
+        self.paint(f, BOLD, format_args!(": {}", e.kind))?;
+        writeln!(f)?;
+        self.location(f, width, "-->", file_name, start)?;
+        self.gutter(f, width, None, "|")?;
+        writeln!(f)?;
+
+        if start.line == end.line {
+            let source = line(start.line);
+            self.gutter(f, width, Some(start.line), "| ")?;
+            writeln!(f, "{}", r.expand_tabs(source))?;
+            // it's just one line, mark the whole span with ^
+            self.gutter(f, width, None, "| ")?;
+            write!(f, "{}", " ".repeat(r.width_until(source, start.column)))?;
+            self.paint(
+                f,
+                self.primary,
+                "^".repeat(r.width_between(source, start.column, end.column)),
+            )?;
+            writeln!(f)?;
+        } else {
+            let source = line(start.line);
+            self.gutter(f, width, Some(start.line), "|   ")?;
+            writeln!(f, "{}", r.expand_tabs(source))?;
+            self.gutter(f, width, None, "|  ")?;
+            self.paint(
+                f,
+                self.primary,
+                format_args!("{}^", "_".repeat(r.width_until(source, start.column))),
+            )?;
+            writeln!(f)?;
+
+            for line_number in start.line + 1..=end.line {
+                self.gutter(f, width, Some(line_number), "| ")?;
+                self.paint(f, self.primary, "|")?;
+                writeln!(f, " {}", r.expand_tabs(line(line_number)))?;
+            }
+
+            self.gutter(f, width, None, "| ")?;
+            self.paint(
+                f,
+                self.primary,
+                format_args!(
+                    "|{}^",
+                    "_".repeat(r.width_until(line(end.line), end.column))
+                ),
+            )?;
+            writeln!(f)?;
+        }
+
+        for label in &context.labels {
+            let label_content = match label.file_id {
+                Some(file_id) if Some(file_id) != context.file_id => {
+                    let (label_file_name, label_content) =
+                        r.files.and_then(|files| files.get(file_id)).unzip();
+                    self.location(f, width, ":::", label_file_name, label.start)?;
+
+                    label_content.unwrap_or_default()
+                }
+                _ => file_content,
+            };
+            let source = source_line(label_content, label.start.line);
+            // multi-line labels only mark their start
+            let marker_len = match label.start.line == label.end.line {
+                true => r.width_between(source, label.start.column, label.end.column),
+                false => 1,
+            };
+
+            self.gutter(f, width, None, "|")?;
+            writeln!(f)?;
+            self.gutter(f, width, Some(label.start.line), "| ")?;
+            writeln!(f, "{}", r.expand_tabs(source))?;
+            self.gutter(f, width, None, "| ")?;
+            write!(
+                f,
+                "{}",
+                " ".repeat(r.width_until(source, label.start.column))
+            )?;
+            self.paint(
+                f,
+                BLUE,
+                format_args!("{} {}", "-".repeat(marker_len), label.message),
+            )?;
+            writeln!(f)?;
+        }
+
+        self.gutter(f, width, None, "|")?;
+        writeln!(f)?;
+
+        self.notes(f, width, context)
+    }
+
+    /// `--> file:line:column`
+    fn location(
+        &self,
+        f: &mut impl Write,
+        width: usize,
+        arrow: &str,
+        file_name: Option<&str>,
+        location: Location,
+    ) -> fmt::Result {
+        self.paint(f, BLUE, format_args!("{}{}", " ".repeat(width), arrow))?;
+        writeln!(
+            f,
+            " {}:{}:{}",
+            file_name.unwrap_or("string"),
+            location.line,
+            location.column
+        )
+    }
+
+    fn notes(&self, f: &mut impl Write, width: usize, context: &ErrorContext) -> fmt::Result {
+        for note in &context.notes {
+            let kind = match note.kind {
+                NoteKind::Note => "note",
+                NoteKind::Help => "help",
+            };
+
+            self.paint(f, BLUE, format_args!("{} =", " ".repeat(width)))?;
+            write!(f, " ")?;
+            self.paint(f, BOLD, kind)?;
+            writeln!(f, ": {}", note.message)?;
+        }
+
+        Ok(())
+    }
+}
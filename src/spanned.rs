@@ -0,0 +1,185 @@
+//! (De)serialization of [`Spanned`] fields
+//!
+//! serde has no notion of locations, so deserializers which know them recognize
+//! a struct with a special name and pass the locations as fields next to the value.
+
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{
+        value::BorrowedStrDeserializer, DeserializeSeed, Error as SerdeErrorTrait,
+        IntoDeserializer, MapAccess, Visitor,
+    },
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{ast::Spanned, Error, Location};
+
+pub(crate) const NAME: &str = "$ron_reboot::Spanned";
+pub(crate) const FIELDS: &[&str] = &[
+    "$start_line",
+    "$start_column",
+    "$end_line",
+    "$end_column",
+    "$value",
+];
+
+/// Captures the locations of a value, for checks after deserialization.
+///
+/// Only deserializers with access to the source support this,
+/// e.g. [`crate::from_str_serde`] and `Spanned<SpannedValue>::into_rust_serde`.
+///
+/// ```
+/// use ron_reboot::{from_str_serde, Error, Location, Spanned};
+/// use serde::{de::Error as _, Deserialize};
+///
+/// #[derive(Deserialize)]
+/// struct Range {
+///     min: u32,
+///     max: Spanned<u32>,
+/// }
+///
+/// let range: Range = from_str_serde("(min: 5, max: 3)").unwrap();
+///
+/// if range.max.value < range.min {
+///     let e = Error::custom("`max` is smaller than `min`")
+///         .context_loc(range.max.start, range.max.end);
+///
+///     assert_eq!(e.start(), Some(Location { line: 1, column: 15 }));
+/// }
+/// ```
+impl<'de, T> Deserialize<'de> for Spanned<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            NAME,
+            FIELDS,
+            SpannedVisitor::new("a value with locations, which this deserializer doesn't support"),
+        )
+    }
+}
+
+/// Serializes just the value
+impl<T> Serialize for Spanned<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.value.serialize(serializer)
+    }
+}
+
+/// Visits the map of [`SpannedDeserializer`]
+pub(crate) struct SpannedVisitor<T> {
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<T> SpannedVisitor<T> {
+    pub(crate) fn new(expecting: &'static str) -> Self {
+        SpannedVisitor {
+            expecting,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> Visitor<'de> for SpannedVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = Spanned<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.expecting)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut next = |field: &'static str| match map.next_key::<&str>()? {
+            Some(key) if key == field => map.next_value::<u32>(),
+            _ => Err(A::Error::missing_field(field)),
+        };
+        let start = Location {
+            line: next(FIELDS[0])?,
+            column: next(FIELDS[1])?,
+        };
+        let end = Location {
+            line: next(FIELDS[2])?,
+            column: next(FIELDS[3])?,
+        };
+
+        match map.next_key::<&str>()? {
+            Some(key) if key == FIELDS[4] => Ok(Spanned {
+                start,
+                value: map.next_value()?,
+                end,
+            }),
+            _ => Err(A::Error::missing_field(FIELDS[4])),
+        }
+    }
+}
+
+/// Visited by [`SpannedVisitor`], `D` deserializes the value located at `start..end`
+pub(crate) struct SpannedDeserializer<D> {
+    locations: [u32; 4],
+    value: Option<D>,
+    /// Index of the next field in [`FIELDS`]
+    field: usize,
+}
+
+impl<D> SpannedDeserializer<D> {
+    pub(crate) fn new(start: Location, end: Location, value: D) -> Self {
+        SpannedDeserializer {
+            locations: [start.line, start.column, end.line, end.column],
+            value: Some(value),
+            field: 0,
+        }
+    }
+}
+
+impl<'de, D> MapAccess<'de> for SpannedDeserializer<D>
+where
+    D: Deserializer<'de, Error = Error>,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match FIELDS.get(self.field) {
+            Some(field) => seed
+                .deserialize(BorrowedStrDeserializer::new(field))
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let field = self.field;
+        self.field += 1;
+
+        match self.locations.get(field) {
+            Some(location) => seed.deserialize(location.into_deserializer()),
+            None => seed.deserialize(
+                self.value
+                    .take()
+                    .expect("called next_value_seed after the value"),
+            ),
+        }
+    }
+}
@@ -54,7 +54,10 @@ pub fn block_comment(input: Input) -> IResultLookahead<Input> {
 
 fn block_comment_tail(input: Input) -> IResultLookahead<()> {
     let comment_end = input.fragment().find("*/").ok_or_else(|| {
-        base_err::<()>(input.slice(input.len() - 1..), Expectation::BlockCommentEnd).unwrap_err()
+        // Point at the last char of the input
+        let last = input.char_indices().last().map_or(0, |(i, _)| i);
+
+        base_err::<()>(input.slice(last..), Expectation::BlockCommentEnd).unwrap_err()
     })?;
     let nested_start = input.fragment().find("/*");
 
@@ -213,4 +216,10 @@ mod tests {
             "/* Hello I am /* a nested */ block comment! */"
         );
     }
+
+    #[test]
+    fn unterminated_block_comment() {
+        assert!(eval!(@result block_comment, "/*").is_err());
+        assert!(eval!(@result block_comment, "/* ä").is_err());
+    }
 }
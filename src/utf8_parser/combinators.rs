@@ -85,6 +85,10 @@ where
     F: FnMut(Input<'a>) -> IResultLookahead<'a, O>,
 {
     move |input: Input| match parser(input) {
+        // Trying alternatives can't help with exceeding the recursion limit
+        Err(InputParseErr::Fatal(e)) if e.recursion_limit().is_some() => {
+            Err(InputParseErr::fatal(e))
+        }
         Err(InputParseErr::Recoverable(e)) | Err(InputParseErr::Fatal(e)) => {
             Err(InputParseErr::recoverable(e))
         }
@@ -114,9 +118,7 @@ where
             Err(InputParseErr::Recoverable(second)) => {
                 Err(InputParseErr::recoverable(ErrorTree::alt(first, second)))
             }
-            Err(InputParseErr::Fatal(second)) => {
-                Err(InputParseErr::fatal(second))
-            }
+            Err(InputParseErr::Fatal(second)) => Err(InputParseErr::fatal(second)),
             res => res,
         },
         res => res,
@@ -150,7 +152,7 @@ where
     move |input: Input| {
         let mut last_discarded_err = None;
 
-        for i in 0..input.len() {
+        for (i, _) in input.char_indices() {
             match input
                 .take_split(i)
                 .then_res(&mut f, |input, res| res.map(|ok| ok.replace(input)))
@@ -358,18 +360,40 @@ pub fn comma_list0<'a, F: 'a, O: 'a>(
 where
     F: FnMut(Input<'a>) -> IResultLookahead<O> + Clone,
 {
-    let with_trailing = many0(terminated(
-        spanned(f.clone()),
-        lookahead(basic::one_char(',')),
-    ));
+    let mut element = spanned(f);
+    let mut comma = lookahead(basic::one_char(','));
 
-    map(
-        pair(with_trailing, opt(spanned(f))),
-        |(mut list, last): (Vec<_>, Option<_>)| {
-            list.extend(last);
-            list
-        },
-    )
+    // Each element is only parsed once; parsing the last element (which is not followed by a comma)
+    // again would take exponential time for nested lists
+    move |mut input: Input<'a>| {
+        let mut list = Vec::new();
+        loop {
+            let ok = match element(input) {
+                Ok(ok) => ok,
+                Err(InputParseErr::Recoverable(e)) => {
+                    return Ok(IOk {
+                        remaining: input,
+                        parsed: list,
+                        discarded_error: Some(e),
+                    })
+                }
+                Err(e) => return Err(e),
+            };
+            list.push(ok.parsed);
+
+            match comma(ok.remaining) {
+                Ok(comma) => input = comma.remaining,
+                Err(InputParseErr::Recoverable(e)) => {
+                    return Ok(IOk {
+                        remaining: ok.remaining,
+                        parsed: list,
+                        discarded_error: Some(e),
+                    })
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -34,12 +34,42 @@ pub fn untagged_struct(input: Input) -> IResultLookahead<Struct> {
     )(input)
 }
 
+/// The fields of a struct without the surrounding parentheses,
+/// used for the whole document by the `implicit_outer_struct` extension
+pub fn implicit_struct(input: Input) -> IResultLookahead<Struct> {
+    map(
+        context("implicit outer struct", comma_list1(ident_val_pair)),
+        |fields| Struct { fields },
+    )(input)
+}
+
+/// Whether `input` starts with `ident:`, i.e. looks like the fields of a struct
+pub fn starts_with_field(input: Input) -> bool {
+    terminated(combinators::spanned(ident::ident), one_char(':'))(input).is_ok()
+}
+
+/// A map key; with the `unquoted_map_keys` extension,
+/// an identifier like `name` is an [`Expr::UnquotedKey`] instead of a unit struct
+fn map_key(input: Input) -> IResultLookahead<Expr> {
+    let unquoted_map_keys = input.unquoted_map_keys();
+
+    utf8_parser::expr(input).map(|ok| {
+        ok.map(|key| match key {
+            Expr::Tagged(tagged)
+                if unquoted_map_keys
+                    && matches!(tagged.untagged.value, Untagged::Unit)
+                    && tagged.ident.value.0 != "None" =>
+            {
+                Expr::UnquotedKey(tagged.ident.value)
+            }
+            key => key,
+        })
+    })
+}
+
 fn key_val_pair(input: Input) -> IResultLookahead<KeyValue<Expr>> {
     let pair = pair(
-        terminated(
-            lookahead(combinators::spanned(utf8_parser::expr)),
-            cut(one_char(':')),
-        ),
+        terminated(lookahead(combinators::spanned(map_key)), cut(one_char(':'))),
         combinators::spanned(utf8_parser::expr),
     );
     map(pair, |(k, v)| KeyValue { key: k, value: v })(input)
@@ -92,7 +122,7 @@ pub fn tuple(input: Input) -> IResultLookahead<Tuple> {
     context(
         "tuple",
         map(
-            combinators::block('(', comma_list0(utf8_parser::expr), ')'),
+            combinators::block('(', combinators::ws(comma_list0(utf8_parser::expr)), ')'),
             |elements| Tuple { elements },
         ),
     )(input)
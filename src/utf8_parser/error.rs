@@ -72,6 +72,9 @@ pub enum Expectation {
     /// An ASCII letter (`[a-zA-Z]`) was expected.
     Alpha,
 
+    /// The first char of an identifier (`_` or XID_Start) was expected.
+    IdentFirstChar,
+
     /// A decimal digit (`[0-9]`) was expected.
     Digit,
 
@@ -127,6 +130,7 @@ impl Display for Expectation {
                 write_pretty_list(f, one_of.iter(), |f, c| write!(f, "{}", c))
             }
             Expectation::Alpha => write!(f, "an ascii letter"),
+            Expectation::IdentFirstChar => write!(f, "a letter or '_'"),
             Expectation::Digit => write!(f, "an ascii digit"),
             Expectation::DigitFirst => write!(f, "a non-zero ascii digit [1-9]"),
             Expectation::HexDigit => write!(f, "a hexadecimal digit"),
@@ -157,6 +161,16 @@ pub enum BaseErrorKind {
     Expected(Expectation),
 
     External(Box<dyn Error + Send + Sync + 'static>),
+
+    /// Expressions are nested deeper than the given limit
+    RecursionLimitExceeded(usize),
+
+    /// A non-ASCII char which looks like RON syntax (e.g. `“` instead of `"`)
+    Confusable {
+        found: char,
+        name: &'static str,
+        looks_like: char,
+    },
 }
 
 impl Display for BaseErrorKind {
@@ -167,6 +181,18 @@ impl Display for BaseErrorKind {
                 writeln!(f, "external error:")?;
                 write!(f, "{}", indent(err))
             }
+            BaseErrorKind::RecursionLimitExceeded(limit) => {
+                write!(f, "exceeded recursion limit of {}", limit)
+            }
+            BaseErrorKind::Confusable {
+                found,
+                name,
+                looks_like,
+            } => write!(
+                f,
+                "found {:?} (U+{:04X} {}), which looks like {:?} but is a different character",
+                found, found as u32, name, looks_like
+            ),
         }
     }
 }
@@ -241,6 +267,19 @@ impl<I> ErrorTree<I> {
         }
     }
 
+    /// The limit if this error was caused by exceeding the recursion limit
+    pub(crate) fn recursion_limit(&self) -> Option<usize> {
+        match self {
+            ErrorTree::Base {
+                kind: BaseErrorKind::RecursionLimitExceeded(limit),
+                ..
+            } => Some(*limit),
+            ErrorTree::Base { .. } => None,
+            ErrorTree::Stack { base, .. } => base.recursion_limit(),
+            ErrorTree::Alt(v) => v.iter().find_map(ErrorTree::recursion_limit),
+        }
+    }
+
     pub(crate) fn alt(first: Self, second: Self) -> Self {
         match (first, second) {
             (ErrorTree::Alt(mut alt), ErrorTree::Alt(alt2)) => {
@@ -369,8 +408,13 @@ impl From<ErrorTree<Location>> for crate::error::Error {
         let max_location = *e.max_location();
         let max_location: Location = max_location.into();
 
+        let kind = match e.recursion_limit() {
+            Some(limit) => crate::error::ErrorKind::RecursionLimitExceeded(limit),
+            None => crate::error::ErrorKind::ParseError(e.to_string()),
+        };
+
         Self {
-            kind: crate::error::ErrorKind::ParseError(e.to_string()),
+            kind,
             context: None,
         }
         .context_loc(
@@ -10,6 +10,13 @@ use crate::{
     utf8_parser::{IOk, IResultLookahead},
 };
 
+/// Maximum nesting depth of expressions used by [`Input::new`]
+///
+/// Every level of nesting takes up to about 40 KiB of stack in release builds
+/// and 120 KiB in debug builds, so this leaves room for the caller
+/// on threads with 2 MiB of stack, the default for spawned threads.
+pub const DEFAULT_RECURSION_LIMIT: usize = 12;
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Offset {
     Absolute(usize),
@@ -36,28 +43,21 @@ impl<'a> From<Input<'a>> for Location {
                     "offset not at char boundary"
                 );
 
-                let line = i.input.bytes().take(offset).filter(|&b| b == b'\n').count() + 1;
-
-                let (byte_ind, char_ind, _c) = get_char_at_offset(i.input, offset);
-
-                if byte_ind != offset {
-                    println!("Input {:?}", i);
-                    assert_eq!(byte_ind, offset, "offset not at char boundary");
-                }
-
-                let line_start = i
-                    .input
-                    .char_indices()
-                    .take(char_ind)
-                    .filter(|(_, c)| *c == '\n')
-                    .map(|(i, _c)| i)
-                    .last()
-                    .map(|i| i + 1)
-                    .unwrap_or(0);
-
-                Location {
-                    line: line as u32,
-                    column: (char_ind - line_start + 1) as u32,
+                let before = &i.input[..offset];
+                let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+                let line = before.matches('\n').count() as u32;
+                // `line_start` is a byte index, the column counts chars
+                let column = before[line_start..].chars().count() as u32;
+
+                match line {
+                    0 => Location {
+                        line: i.start.line,
+                        column: i.start.column + column,
+                    },
+                    _ => Location {
+                        line: i.start.line + line,
+                        column: column + 1,
+                    },
                 }
             }
             Offset::Relative(_) => todo!(),
@@ -68,6 +68,16 @@ impl<'a> From<Input<'a>> for Location {
 #[derive(Clone, Copy, Eq, PartialEq)]
 pub struct Input<'a> {
     offset: Offset,
+    /// Nesting depth of the expression being parsed
+    depth: usize,
+    recursion_limit: Option<usize>,
+    /// Parse the fields of a struct without parentheses at the top level,
+    /// even if the document doesn't enable `implicit_outer_struct`
+    implicit_outer_struct: bool,
+    /// Parse bare identifiers as map keys, see [`crate::ast::Expr::UnquotedKey`]
+    unquoted_map_keys: bool,
+    /// Location of the start of `input`, which may be a part of a larger document
+    start: Location,
 
     /// the complete input
     ///
@@ -80,11 +90,78 @@ impl<'a> Input<'a> {
     pub fn new(input: &'a str) -> Self {
         Input {
             offset: Offset::Absolute(0),
+            depth: 0,
+            recursion_limit: Some(DEFAULT_RECURSION_LIMIT),
+            implicit_outer_struct: false,
+            unquoted_map_keys: false,
+            start: Location { line: 1, column: 1 },
             input,
             fragment: input,
         }
     }
 
+    /// Limits how deep expressions may be nested, `None` to disable the limit
+    pub fn with_recursion_limit(self, recursion_limit: Option<usize>) -> Self {
+        Input {
+            recursion_limit,
+            ..self
+        }
+    }
+
+    /// Locations begin at `start` instead of `1:1`, for parsing a part of a document
+    pub fn with_start(self, start: Location) -> Self {
+        Input { start, ..self }
+    }
+
+    pub fn recursion_limit(&self) -> Option<usize> {
+        self.recursion_limit
+    }
+
+    pub fn with_implicit_outer_struct(self, implicit_outer_struct: bool) -> Self {
+        Input {
+            implicit_outer_struct,
+            ..self
+        }
+    }
+
+    pub fn implicit_outer_struct(&self) -> bool {
+        self.implicit_outer_struct
+    }
+
+    pub fn with_unquoted_map_keys(self, unquoted_map_keys: bool) -> Self {
+        Input {
+            unquoted_map_keys,
+            ..self
+        }
+    }
+
+    pub fn unquoted_map_keys(&self) -> bool {
+        self.unquoted_map_keys
+    }
+
+    /// The input for the contents of the expression at `self`,
+    /// `None` if the expression is nested deeper than the recursion limit
+    ///
+    /// The depth of an expression is the number of containers around it, the deserializer
+    /// checks the same in `Context::nested`.
+    pub fn nested(self) -> Option<Self> {
+        match self.recursion_limit {
+            Some(limit) if self.depth > limit => None,
+            _ => Some(Input {
+                depth: self.depth + 1,
+                ..self
+            }),
+        }
+    }
+
+    /// Continue at the depth of `outer` after parsing a nested expression
+    pub fn unnested(self, outer: Self) -> Self {
+        Input {
+            depth: outer.depth,
+            ..self
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.fragment.is_empty()
     }
@@ -122,17 +199,16 @@ impl<'a> Input<'a> {
         let consumed_len = str_offset(self.fragment, next_fragment);
         if consumed_len == 0 {
             return Input {
-                offset: self.offset,
-                input: self.input,
                 fragment: next_fragment,
+                ..*self
             };
         }
         let next_offset = self.offset + consumed_len;
 
         Input {
             offset: next_offset,
-            input: self.input,
             fragment: next_fragment,
+            ..*self
         }
     }
 }
@@ -189,18 +265,6 @@ pub fn position(input: Input) -> IResultLookahead<Input> {
     Ok(input.take_split(0))
 }
 
-/// returns (byte index, char index, char)
-#[inline]
-fn get_char_at_offset(input: &str, offset: usize) -> (usize, usize, char) {
-    input
-        .char_indices()
-        .enumerate()
-        .map(|(c_ind, (b_ind, c))| (b_ind, c_ind, c))
-        // we now have an iterator of (byte index, char index, char)
-        .find(|(i, _, _)| *i >= offset)
-        .unwrap_or((input.len(), input.chars().count(), '�'))
-}
-
 /// Byte offset between string slices
 fn str_offset(first: &str, second: &str) -> usize {
     let first = first.as_ptr();
@@ -211,10 +275,7 @@ fn str_offset(first: &str, second: &str) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        location::Location,
-        utf8_parser::{input::get_char_at_offset, Input},
-    };
+    use crate::{location::Location, utf8_parser::Input};
 
     #[test]
     fn test_location() {
@@ -242,22 +303,36 @@ mod tests {
     }
 
     #[test]
-    fn test_char_offset_basic() {
-        assert_eq!(get_char_at_offset("123", 1), (1, 1, '2'));
-    }
-
-    #[test]
-    fn test_char_offset_start() {
-        assert_eq!(get_char_at_offset("123", 0), (0, 0, '1'));
-    }
-
-    #[test]
-    fn test_char_offset_end() {
-        assert_eq!(get_char_at_offset("123", 2), (2, 2, '3'));
+    fn test_location_multibyte() {
+        let input = Input::new("\"äöü\nß\" Ä");
+        assert_eq!(
+            Location::from(input.take_split(7).remaining),
+            Location { line: 1, column: 5 }
+        );
+        assert_eq!(
+            Location::from(input.take_split(8).remaining),
+            Location { line: 2, column: 1 }
+        );
+        assert_eq!(
+            Location::from(input.take_split(12).remaining),
+            Location { line: 2, column: 4 }
+        );
     }
 
     #[test]
-    fn test_char_offset_eof() {
-        assert_eq!(get_char_at_offset("123", 3), (3, 3, '�'));
+    fn test_location_with_start() {
+        let input = Input::new("(\n a)").with_start(Location { line: 3, column: 7 });
+        assert_eq!(
+            Location::from(input.take_split(0).remaining),
+            Location { line: 3, column: 7 }
+        );
+        assert_eq!(
+            Location::from(input.take_split(1).remaining),
+            Location { line: 3, column: 8 }
+        );
+        assert_eq!(
+            Location::from(input.take_split(3).remaining),
+            Location { line: 4, column: 2 }
+        );
     }
 }
@@ -1,11 +1,17 @@
-pub use self::error::{ErrorTree, InputParseError};
 use self::{
     containers::{list, rmap, tuple, untagged_struct},
     error::{BaseErrorKind, Expectation, InputParseErr},
     input::Input,
-    primitive::{bool, decimal, escaped_string, signed_integer, unescaped_str, unsigned_integer},
+    primitive::{
+        bool, char_literal, decimal, escaped_string, non_finite, signed_integer, unescaped_str,
+        unsigned_integer,
+    },
     ron::expr,
 };
+pub use self::{
+    error::{ErrorTree, InputParseError},
+    input::DEFAULT_RECURSION_LIMIT,
+};
 use crate::{ast, ast::Ron, utf8_parser::ok::IOk, Error};
 
 //pub type IResultFatal<'a, O> = Result<(Input<'a>, O), InputParseError<'a>>;
@@ -14,8 +20,7 @@ type OutputResult<'a, O> = Result<O, InputParseErr<'a>>;
 
 /// Basic parsers which receive `Input`
 mod basic;
-/// Tables for fast lookup of char categories
-mod char_categories;
+use crate::char_categories;
 /// Parser combinators which take one or more parsers and modify / combine them
 mod combinators;
 /// RON container parsers
@@ -44,11 +49,87 @@ mod util;
 pub mod test_util;
 
 pub fn ast_from_str(input: &str) -> Result<Ron, crate::error::Error> {
-    let pt: pt::Ron = ron::ron(input)
-        .map_err(ErrorTree::calc_locations)
-        .map_err(Error::from)
-        .map_err(|e| e.context_file_content(input.to_owned()))?;
+    ast_from_str_with_recursion_limit(input, Some(DEFAULT_RECURSION_LIMIT))
+}
+
+/// Like [`ast_from_str`], but with a custom limit for the nesting depth of expressions
+/// (`None` for no limit).
+///
+/// Deeply nested input can overflow the stack without a limit.
+pub fn ast_from_str_with_recursion_limit(
+    input: &str,
+    recursion_limit: Option<usize>,
+) -> Result<Ron<'_>, crate::error::Error> {
+    ast_from_input(Input::new(input).with_recursion_limit(recursion_limit))
+}
+
+/// Parses `input`, a part of a larger document starting at `start`,
+/// so that the locations in the AST and errors point into that document
+#[cfg(feature = "utf8_parser_serde1")]
+pub(crate) fn ast_from_str_at(
+    input: &str,
+    start: crate::Location,
+    options: serde::Options,
+) -> Result<Ron<'_>, crate::error::Error> {
+    ast_from_input(input_with_options(input, start, options))
+}
+
+/// Parses the first document of `input`, a part of a larger document starting at `start`,
+/// returning it and its length in bytes, including the whitespace after it
+///
+/// Errors come with whether the parser reached the end of `input`,
+/// i.e. whether the document may be unfinished rather than invalid.
+#[cfg(feature = "utf8_parser_serde1")]
+pub(crate) fn ast_from_str_partial(
+    input: &str,
+    start: crate::Location,
+    options: serde::Options,
+) -> Result<(Ron<'_>, usize), (crate::error::Error, bool)> {
+    let input = input_with_options(input, start, options);
+    let (pt, remaining) = ron::ron_partial(input).map_err(|e| {
+        let unfinished = e.max_location().is_empty();
+
+        (parse_error(input, e), unfinished)
+    })?;
+
+    Ok((pt.into(), input.len() - remaining.len()))
+}
+
+#[cfg(feature = "utf8_parser_serde1")]
+fn input_with_options(input: &str, start: crate::Location, options: serde::Options) -> Input<'_> {
+    Input::new(input)
+        .with_recursion_limit(options.recursion_limit)
+        .with_implicit_outer_struct(options.implicit_outer_struct)
+        .with_unquoted_map_keys(options.unquoted_map_keys)
+        .with_start(start)
+}
+
+/// Length in bytes of the whitespace & comments at the start of `input`
+#[cfg(feature = "utf8_parser_serde1")]
+pub(crate) fn whitespace_len(input: &str) -> usize {
+    let input = Input::new(input);
+
+    basic::multispacews0(input).map_or(0, |ok| input.len() - ok.remaining.len())
+}
+
+fn ast_from_input(input: Input<'_>) -> Result<Ron<'_>, crate::error::Error> {
+    let pt: pt::Ron = ron::ron(input).map_err(|e| parse_error(input, e))?;
     let ast: ast::Ron = pt.into();
 
     Ok(ast)
 }
+
+/// Converts the error from parsing `input`, explaining the `implicit_outer_struct` extension
+/// if the document looks like the fields of a struct
+fn parse_error<'a>(input: Input<'a>, e: InputParseError<'a>) -> Error {
+    let e = Error::from(ErrorTree::calc_locations(e));
+
+    if ron::missing_outer_struct(input) {
+        e.context_note("the document looks like the fields of a struct without `(` and `)`")
+            .context_help(
+                "put `(` and `)` around the fields or add `#![enable(implicit_outer_struct)]`",
+            )
+    } else {
+        e
+    }
+}
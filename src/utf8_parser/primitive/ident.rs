@@ -1,18 +1,13 @@
 use crate::utf8_parser::{
-    char_categories::{is_ident_first_char, is_ident_other_char},
-    combinators::{context, map, preceded, recognize, take1_if, take_while},
+    basic::tag,
+    char_categories::{is_ident_first_char, is_ident_other_char, is_ident_raw_char},
+    combinators::{alt2, context, lookahead, map, preceded, recognize, take1_if, take_while},
     pt::Ident,
     Expectation, IResultLookahead, Input,
 };
-use crate::utf8_parser::basic::tag;
-use crate::utf8_parser::char_categories::is_ident_raw_char;
-use crate::utf8_parser::combinators::{alt2, lookahead};
 
 fn ident_first_char(input: Input) -> IResultLookahead<Input> {
-    take1_if(
-        is_ident_first_char,
-        Expectation::OneOfExpectations(&[Expectation::Alpha, Expectation::Char('_')]),
-    )(input)
+    take1_if(is_ident_first_char, Expectation::IdentFirstChar)(input)
 }
 
 fn raw_ident_inner(input: Input) -> IResultLookahead<Input> {
@@ -28,7 +23,10 @@ fn ast_ident_from_input(input: Input) -> Ident {
 }
 
 pub fn ident(input: Input) -> IResultLookahead<Ident> {
-    context("ident", map(alt2(raw_ident_inner, ident_inner), ast_ident_from_input))(input)
+    context(
+        "ident",
+        map(alt2(raw_ident_inner, ident_inner), ast_ident_from_input),
+    )(input)
 }
 
 #[cfg(test)]
@@ -64,6 +62,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unicode_ident() {
+        assert_eq!(eval!(ident, "Größe"), Ident("Größe"));
+        assert_eq!(eval!(ident, "名前"), Ident("名前"));
+        assert_eq!(eval!(ident, "_ñ1"), Ident("_ñ1"));
+        assert_eq!(eval!(ident, "r#größe-1"), Ident("größe-1"));
+        // stops at chars which are not XID_Continue
+        assert_eq!(eval!(ident, "a·b€"), Ident("a·b"));
+
+        assert!(eval!(@result ident, "\u{0301}a").is_err());
+        assert!(eval!(@result ident, "€").is_err());
+    }
+
     #[test]
     fn raw_ident() {
         assert_eq!(eval!(ident, "r#Config"), Ident("Config"));
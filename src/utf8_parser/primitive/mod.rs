@@ -1,11 +1,16 @@
 pub use self::{
     ident::ident,
-    number::{decimal, signed_integer, unsigned_integer},
+    number::{decimal, non_finite, signed_integer, unsigned_integer},
     raw_str::parse_raw_string as raw_str,
     str::unescaped_str,
-    string::parse_string as escaped_string,
+    string::{parse_char as char_literal, parse_string as escaped_string},
+};
+use crate::utf8_parser::{
+    char_categories::is_ident_other_char,
+    combinators::{context, map_res, take_while},
+    util::base_err_res,
+    Expectation, IResultLookahead, Input,
 };
-use crate::utf8_parser::{basic::one_of_tags, combinators::context, IResultLookahead, Input};
 
 pub mod ident;
 pub mod number;
@@ -13,6 +18,16 @@ mod raw_str;
 mod str;
 mod string;
 
+/// `true` or `false`, but not e.g. the identifier `trueish`
 pub fn bool(input: Input) -> IResultLookahead<bool> {
-    context("bool", one_of_tags(&["true", "false"], &[true, false]))(input)
+    context(
+        "bool",
+        map_res(take_while(is_ident_other_char), |word| {
+            match word.fragment() {
+                "true" => Ok(true),
+                "false" => Ok(false),
+                _ => base_err_res(word, Expectation::OneOfTags(&["true", "false"])),
+            }
+        }),
+    )(input)
 }
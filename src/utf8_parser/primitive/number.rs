@@ -2,12 +2,13 @@ use std::str::FromStr;
 
 use crate::utf8_parser::{
     basic::{one_char, one_of_chars},
-    char_categories::{is_digit, is_digit_first},
+    char_categories::{is_digit, is_digit_first, is_ident_other_char},
     combinators::{
         alt2, context, lookahead, map, map_res, opt, pair, preceded, recognize, take1_if,
         take_while, terminated,
     },
-    pt::{Decimal, Sign, SignedInteger, UnsignedInteger},
+    pt::{Decimal, NonFinite, Sign, SignedInteger, UnsignedInteger},
+    util::base_err_res,
     BaseErrorKind, ErrorTree, Expectation, IResultLookahead, Input, InputParseErr, OutputResult,
 };
 
@@ -59,7 +60,7 @@ fn decimal_unsigned_no_leading_zero(input: Input) -> IResultLookahead<u64> {
 fn alt_radix_unsigned(input: Input) -> IResultLookahead<u64> {
     map_res(
         pair(
-                lookahead(preceded(one_char('0'), one_of_chars("box", &[2, 8, 16]))),
+            lookahead(preceded(one_char('0'), one_of_chars("box", &[2, 8, 16]))),
             take_while(|c| c.is_ascii_hexdigit()),
         ),
         parse_u64_radix,
@@ -67,9 +68,10 @@ fn alt_radix_unsigned(input: Input) -> IResultLookahead<u64> {
 }
 
 pub fn unsigned_integer(input: Input) -> IResultLookahead<UnsignedInteger> {
-    map(alt2(alt_radix_unsigned, decimal_unsigned_no_leading_zero), |number| UnsignedInteger {
-        number,
-    })(input)
+    map(
+        alt2(alt_radix_unsigned, decimal_unsigned_no_leading_zero),
+        |number| UnsignedInteger { number },
+    )(input)
 }
 
 pub fn signed_integer(input: Input) -> IResultLookahead<SignedInteger> {
@@ -133,6 +135,21 @@ pub fn decimal(input: Input) -> IResultLookahead<Decimal> {
     context("decimal", alt2(decimal_frac, decimal_std))(input)
 }
 
+/// `inf`, `+inf`, `-inf` or `NaN`, but not e.g. the identifier `info`
+pub fn non_finite(input: Input) -> IResultLookahead<NonFinite> {
+    context(
+        "non-finite float",
+        map_res(
+            pair(opt(lookahead(sign)), take_while(is_ident_other_char)),
+            |(sign, word)| match (sign, word.fragment()) {
+                (sign, "inf") => Ok(NonFinite::Infinity(sign)),
+                (None, "NaN") => Ok(NonFinite::NaN),
+                _ => base_err_res(word, Expectation::OneOfTags(&["inf", "NaN"])),
+            },
+        ),
+    )(input)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,6 +162,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn exprs_non_finite() {
+        for input in ["inf", "+inf", "-inf", "NaN"] {
+            assert_eq!(
+                Expr::NonFinite(eval!(non_finite, input)),
+                eval!(expr, input)
+            );
+        }
+
+        // Identifiers starting with `inf` / `NaN`
+        for input in ["info", "NaNa", "inf_"] {
+            assert!(matches!(eval!(expr, input), Expr::Tagged(_)), "{}", input);
+        }
+        for input in ["-info", "+NaN", "-NaN"] {
+            assert!(eval!(@result expr, input).is_err(), "{}", input);
+        }
+    }
+
     #[test]
     fn exprs_int() {
         for input in ["-4123", "111", "+821", "0"] {
@@ -73,4 +73,9 @@ mod tests {
             List::new_test(vec![Expr::Str(r##"Very \ raw string"##), Expr::Str("b")])
         );
     }
+
+    #[test]
+    fn test_raw_unterminated() {
+        assert!(eval!(@result parse_raw_string, "r\"🌀").is_err());
+    }
 }
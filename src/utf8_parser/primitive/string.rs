@@ -1,8 +1,8 @@
 use crate::utf8_parser::{
     basic::{multispace1, one_char, one_of_chars},
     combinators::{
-        alt2, context, cut, delimited, fold_many0, lookahead, map, map_res, preceded, take_while,
-        take_while_m_n,
+        alt2, context, cut, delimited, fold_many0, lookahead, map, map_res, preceded, take1_if,
+        take_while, take_while_m_n,
     },
     util::base_err_res,
     BaseErrorKind, ErrorTree, Expectation, IResultLookahead, Input, InputParseErr,
@@ -43,8 +43,8 @@ fn parse_escaped_char(input: Input) -> IResultLookahead<char> {
         alt2(
             lookahead(parse_unicode),
             one_of_chars(
-                "nrtbf\\/\"",
-                &['\n', '\r', '\t', '\u{08}', '\u{0C}', '\\', '/', '"'],
+                "nrtbf\\/\"'",
+                &['\n', '\r', '\t', '\u{08}', '\u{0C}', '\\', '/', '"', '\''],
             ),
         ),
     )(input)
@@ -136,3 +136,20 @@ pub fn parse_string(input: Input) -> IResultLookahead<String> {
         delimited(one_char('"'), inner_string, one_char('"')),
     )(input)
 }
+
+/// Parse a char literal, e.g. `'a'`, `'\''` or `'\u{1F980}'`
+pub fn parse_char(input: Input) -> IResultLookahead<char> {
+    let unescaped = map(
+        take1_if(|c| c != '\'' && c != '\\', Expectation::Something),
+        |c: Input| c.fragment().chars().next().unwrap(),
+    );
+
+    context(
+        "char",
+        delimited(
+            one_char('\''),
+            alt2(lookahead(parse_escaped_char), unescaped),
+            one_char('\''),
+        ),
+    )(input)
+}
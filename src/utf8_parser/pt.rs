@@ -245,6 +245,21 @@ impl Decimal {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NonFinite {
+    Infinity(Option<Sign>),
+    NaN,
+}
+
+impl From<NonFinite> for ast::NonFinite {
+    fn from(n: NonFinite) -> ast::NonFinite {
+        match n {
+            NonFinite::Infinity(sign) => ast::NonFinite::Infinity(sign.map(Into::into)),
+            NonFinite::NaN => ast::NonFinite::NaN,
+        }
+    }
+}
+
 impl From<Decimal> for ast::Decimal {
     fn from(d: Decimal) -> ast::Decimal {
         ast::Decimal {
@@ -417,7 +432,13 @@ pub struct Tagged<'a> {
 }
 
 impl<'a> Tagged<'a> {
+    /// `Some(x)` or `None`, but not the raw identifiers `r#Some(x)` or `r#None`
     pub fn is_optional(&self) -> bool {
+        // The span of a raw identifier includes the `r#`
+        if self.ident.start.offset_to(&self.ident.end) != self.ident.value.0.len() {
+            return false;
+        }
+
         match (self.ident.value.0, &self.untagged.value) {
             ("Some", Untagged::Tuple(Tuple { elements })) if elements.len() == 1 => true,
             ("None", Untagged::Unit) => true,
@@ -447,7 +468,7 @@ impl<'a> From<Tagged<'a>> for ast::Tagged<'a> {
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Expr<'a> {
-    Tagged(Tagged<'a>),
+    Tagged(Box<Tagged<'a>>),
     Bool(bool),
     Tuple(Tuple<'a>),
     List(List<'a>),
@@ -458,7 +479,10 @@ pub enum Expr<'a> {
     Str(&'a str),
     /// Escaped string
     String(String),
+    Char(char),
     Decimal(Decimal),
+    NonFinite(NonFinite),
+    UnquotedKey(Ident<'a>),
 }
 
 impl<'a> From<Expr<'a>> for ast::Expr<'a> {
@@ -467,7 +491,7 @@ impl<'a> From<Expr<'a>> for ast::Expr<'a> {
             Expr::Tagged(t) if t.is_optional() => {
                 ast::Expr::Optional(t.into_optional().map(|e| Box::new(e.into())))
             }
-            Expr::Tagged(t) => ast::Expr::Tagged(t.into()),
+            Expr::Tagged(t) => ast::Expr::Tagged((*t).into()),
             Expr::Bool(x) => ast::Expr::Bool(x),
             Expr::Tuple(x) if x.elements.is_empty() => ast::Expr::Unit,
             Expr::Tuple(x) => ast::Expr::Tuple(x.into()),
@@ -477,7 +501,10 @@ impl<'a> From<Expr<'a>> for ast::Expr<'a> {
             Expr::Integer(x) => ast::Expr::Integer(x.into()),
             Expr::Str(x) => ast::Expr::Str(x.into()),
             Expr::String(x) => ast::Expr::String(x.into()),
+            Expr::Char(x) => ast::Expr::Char(x),
             Expr::Decimal(x) => ast::Expr::Decimal(x.into()),
+            Expr::NonFinite(x) => ast::Expr::NonFinite(x.into()),
+            Expr::UnquotedKey(x) => ast::Expr::UnquotedKey(x.into()),
         }
     }
 }
@@ -1,24 +1,34 @@
 use crate::utf8_parser::{
     basic::{one_char, one_of_chars, one_of_tags, tag},
     bool,
-    char_categories::is_ident_first_char,
-    combinators,
+    char_categories::{confusable, is_ident_first_char},
+    char_literal, combinators,
     combinators::{
-        alt2, comma_list1, context, context_final, cut, delimited, lookahead, many0, map, pair,
-        preceded, take1_if,
+        alt2, comma_list1, context, context_final, cut, delimited, lookahead, many0, map, preceded,
+        take1_if,
     },
-    containers::tagged,
-    decimal, escaped_string, list,
+    containers::{implicit_struct, starts_with_field, tagged},
+    decimal, escaped_string, list, non_finite,
     primitive::raw_str,
-    pt::{Attribute, Expr, Extension, Ron, SignedInteger, UnsignedInteger},
-    rmap, signed_integer, tuple, unescaped_str, unsigned_integer, untagged_struct, ErrorTree,
-    Expectation, IResultLookahead, Input, InputParseErr, InputParseError,
+    pt::{Attribute, Expr, Extension, Ron, SignedInteger, Spanned, UnsignedInteger, Untagged},
+    rmap, signed_integer, tuple, unescaped_str, unsigned_integer, untagged_struct, BaseErrorKind,
+    ErrorTree, Expectation, IOk, IResultLookahead, Input, InputParseErr, InputParseError,
 };
 
 fn extension_name(input: Input) -> IResultLookahead<Extension> {
     one_of_tags(
-        &["unwrap_newtypes", "implicit_some"],
-        &[Extension::UnwrapNewtypes, Extension::ImplicitSome],
+        &[
+            "unwrap_newtypes",
+            "implicit_some",
+            "implicit_outer_struct",
+            "unquoted_map_keys",
+        ],
+        &[
+            Extension::UnwrapNewtypes,
+            Extension::ImplicitSome,
+            Extension::ImplicitOuterStruct,
+            Extension::UnquotedMapKeys,
+        ],
     )(input)
 }
 
@@ -54,6 +64,7 @@ enum ExprClass {
     StructTuple,
     Map,
     StrString,
+    Char,
     List,
     Bool,
     /// Signed or Decimal
@@ -67,11 +78,12 @@ enum ExprClass {
 impl ExprClass {
     pub fn parse(input: Input) -> IResultLookahead<Self> {
         let all_but_ident = one_of_chars(
-            "({\"[tf+-.0123456789",
+            "({\"'[tf+-.0123456789",
             &[
                 ExprClass::StructTuple,
                 ExprClass::Map,
                 ExprClass::StrString,
+                ExprClass::Char,
                 ExprClass::List,
                 ExprClass::Bool,
                 ExprClass::Bool,
@@ -94,10 +106,7 @@ impl ExprClass {
         alt2(
             lookahead(all_but_ident),
             map(
-                take1_if(
-                    is_ident_first_char,
-                    Expectation::OneOfExpectations(&[Expectation::Alpha, Expectation::Char('_')]),
-                ),
+                take1_if(is_ident_first_char, Expectation::IdentFirstChar),
                 |_| ExprClass::LeadingIdent,
             ),
         )(input)
@@ -121,44 +130,165 @@ fn expr_inner(input: Input) -> IResultLookahead<Expr> {
             map(lookahead(unescaped_str), Expr::Str),
             map(escaped_string, Expr::String),
         )(input),
+        ExprClass::Char => map(char_literal, Expr::Char)(input),
         ExprClass::List => map(list, Expr::List)(input),
-        ExprClass::Bool => map(bool, Expr::Bool)(input),
+        // Identifiers may start with `t` or `f` as well
+        ExprClass::Bool => alt2(map(lookahead(bool), Expr::Bool), leading_ident)(input),
         ExprClass::SignedDec => alt2(
-            map(decimal, Expr::Decimal),
-            map(signed_integer, SignedInteger::to_expr),
+            map(lookahead(non_finite), Expr::NonFinite),
+            alt2(
+                map(decimal, Expr::Decimal),
+                map(signed_integer, SignedInteger::to_expr),
+            ),
         )(input),
         ExprClass::Dec => map(decimal, Expr::Decimal)(input),
         ExprClass::UnsignedDec => alt2(
             map(decimal, Expr::Decimal),
             map(unsigned_integer, UnsignedInteger::to_expr),
         )(input),
-        ExprClass::LeadingIdent => {
-            alt2(map(raw_str, Expr::Str), map(tagged, Expr::Tagged))(input)
-        }
+        ExprClass::LeadingIdent => leading_ident(input),
     }
 }
 
+fn leading_ident(input: Input) -> IResultLookahead<Expr> {
+    alt2(
+        map(lookahead(non_finite), Expr::NonFinite),
+        alt2(
+            map(raw_str, Expr::Str),
+            map(tagged, |t| Expr::Tagged(Box::new(t))),
+        ),
+    )(input)
+}
+
 pub fn expr(input: Input) -> IResultLookahead<Expr> {
-    context_final("expression", true, expr_inner)(input)
+    let nested = match (input.nested(), input.recursion_limit()) {
+        (Some(nested), _) => nested,
+        (None, limit) => {
+            return Err(InputParseErr::fatal(ErrorTree::Base {
+                location: input,
+                kind: BaseErrorKind::RecursionLimitExceeded(limit.unwrap_or_default()),
+            }))
+        }
+    };
+
+    context_final("expression", true, expr_inner)(nested).map(|ok| IOk {
+        remaining: ok.remaining.unnested(input),
+        ..ok
+    })
+}
+
+fn enables(attributes: &[Spanned<Attribute>], extension: Extension) -> bool {
+    attributes.iter().any(|attribute| match &attribute.value {
+        Attribute::Enable(extensions) => extensions.value.iter().any(|e| e.value == extension),
+    })
 }
 
 fn ron_inner(input: Input) -> IResultLookahead<Ron> {
-    map(
-        pair(
-            many0(combinators::spanned(attribute)),
-            combinators::spanned(expr),
-        ),
-        |(attributes, expr)| Ron { attributes, expr },
-    )(input)
+    let attributes = many0(combinators::spanned(attribute))(input)?;
+    let implicit_outer_struct = input.implicit_outer_struct()
+        || enables(&attributes.parsed, Extension::ImplicitOuterStruct);
+    let unquoted_map_keys =
+        input.unquoted_map_keys() || enables(&attributes.parsed, Extension::UnquotedMapKeys);
+
+    attributes.and_then(
+        |input| {
+            let input = input.with_unquoted_map_keys(unquoted_map_keys);
+
+            if implicit_outer_struct && starts_with_field(input) {
+                combinators::spanned(map(implicit_struct, Expr::Struct))(input)
+            } else {
+                combinators::spanned(expr)(input)
+            }
+        },
+        |attributes, expr| Ron { attributes, expr },
+    )
 }
 
-pub fn ron(input: &str) -> Result<Ron, InputParseError> {
-    let input = Input::new(input);
+/// Whether `input` looks like the fields of a struct without parentheses,
+/// but doesn't enable `implicit_outer_struct`
+pub fn missing_outer_struct(input: Input) -> bool {
+    match many0(combinators::spanned(attribute))(input) {
+        Ok(ok) => {
+            !input.implicit_outer_struct()
+                && !enables(&ok.parsed, Extension::ImplicitOuterStruct)
+                && starts_with_field(ok.remaining)
+        }
+        Err(_) => false,
+    }
+}
 
+pub fn ron(input: Input) -> Result<Ron, InputParseError> {
     match ron_inner(input) {
         Ok(ok) if ok.remaining.is_empty() => Ok(ok.parsed),
-        Ok(ok) => Err(ErrorTree::expected(ok.remaining, Expectation::Eof)),
-        Err(InputParseErr::Fatal(e)) | Err(InputParseErr::Recoverable(e)) => Err(e),
+        Ok(ok) => Err(explain_confusable(ErrorTree::expected(
+            ok.remaining,
+            Expectation::Eof,
+        ))),
+        Err(InputParseErr::Fatal(e)) | Err(InputParseErr::Recoverable(e)) => {
+            Err(explain_confusable(e))
+        }
+    }
+}
+
+/// Like [`ron`], but stops after the first document (and the whitespace after it),
+/// returning the remaining input
+///
+/// Documents have to be separated by whitespace, which also catches documents cut short
+/// (e.g. `Name(a: 1` parses as `Name` followed by `(a: 1`).
+pub fn ron_partial(input: Input) -> Result<(Ron, Input), InputParseError> {
+    match ron_inner(input) {
+        Ok(ok)
+            if !ok.remaining.is_empty() && ok.parsed.expr.end.offset() == ok.remaining.offset() =>
+        {
+            Err(explain_confusable(missing_whitespace(
+                &ok.parsed.expr.value,
+                ok.remaining,
+            )))
+        }
+        Ok(ok) => Ok((ok.parsed, ok.remaining)),
+        Err(InputParseErr::Fatal(e)) | Err(InputParseErr::Recoverable(e)) => {
+            Err(explain_confusable(e))
+        }
+    }
+}
+
+/// The error for `remaining`, which follows `expr` without whitespace in between
+///
+/// If `expr` is a name followed by `(`, the struct or tuple after the name didn't parse,
+/// and its error (e.g. at the end of a document cut short) is the more helpful one.
+fn missing_whitespace<'a>(expr: &Expr, remaining: Input<'a>) -> InputParseError<'a> {
+    let unit = matches!(expr, Expr::Tagged(t) if matches!(t.untagged.value, Untagged::Unit));
+
+    if unit && remaining.chars().next() == Some('(') {
+        let untagged = alt2(map(untagged_struct, |_| ()), map(tuple, |_| ()))(remaining);
+
+        if let Err(InputParseErr::Fatal(e) | InputParseErr::Recoverable(e)) = untagged {
+            return e;
+        }
+    }
+
+    ErrorTree::expected(remaining, Expectation::Multispace)
+}
+
+/// If the error points at a char that looks like RON syntax (e.g. `“`), that char is most
+/// likely the problem, and saying so is more helpful than listing what was expected.
+fn explain_confusable(e: InputParseError) -> InputParseError {
+    let location = *e.max_location();
+    let found = match location.chars().next() {
+        Some(found) => found,
+        None => return e,
+    };
+
+    match confusable(found) {
+        Some((looks_like, name)) => ErrorTree::Base {
+            location,
+            kind: BaseErrorKind::Confusable {
+                found,
+                name,
+                looks_like,
+            },
+        },
+        None => e,
     }
 }
 
@@ -185,5 +315,14 @@ mod tests {
             ),
             Attribute::enables_test(vec![Extension::ImplicitSome, Extension::UnwrapNewtypes])
         );
+
+        assert_eq!(
+            eval!(attribute, "#![enable(implicit_outer_struct)]"),
+            Attribute::enables_test(vec![Extension::ImplicitOuterStruct])
+        );
+        assert_eq!(
+            eval!(attribute, "#![enable(unquoted_map_keys)]"),
+            Attribute::enables_test(vec![Extension::UnquotedMapKeys])
+        );
     }
 }
@@ -1,9 +1,11 @@
 #![allow(clippy::type_complexity)]
 
+use std::cell::RefCell;
+
 use serde::{
     de::{
-        DeserializeSeed, EnumAccess, Error as SerdeErrorTrait, MapAccess, SeqAccess, VariantAccess,
-        Visitor,
+        value::BorrowedStrDeserializer, DeserializeSeed, EnumAccess, Error as SerdeErrorTrait,
+        MapAccess, SeqAccess, Unexpected, VariantAccess, Visitor,
     },
     forward_to_deserialize_any, Deserialize, Deserializer,
 };
@@ -12,70 +14,328 @@ use serde::{
 //use crate::error::{ron_err, ErrorKind};
 use crate::{
     ast::Untagged,
-    error::Error,
+    error::{Error, ErrorKind},
     utf8_parser::{
         ast,
         ast::{Expr::*, Integer},
-        ast_from_str,
+        ast_from_str_at,
     },
 };
-use crate::ast::{Attribute, Extension};
+use crate::{
+    spanned::{self, SpannedDeserializer},
+    utf8_parser::serde::{
+        raw::{self, Source},
+        DuplicateKeys, Options,
+    },
+    util::similar_name,
+    Location, PathSegment,
+};
 
 pub fn from_str<'a, T>(s: &'a str) -> Result<T, crate::error::Error>
 where
     T: Deserialize<'a>,
 {
-    let mut ron = ast_from_str(s)
-        .map_err(Error::from)
-        .map_err(|e| e.context_file_content(s.to_owned()))?;
-
-    T::deserialize(RonDeserializer::from_ron(&mut ron))
-        .map_err(|e| e.context_file_content(s.to_owned()))
-}
-
-#[derive(Clone, Copy, Debug, Default)]
-struct Extensions {
-    implicit_some: bool,
-    unwrap_newtypes: bool,
-}
-
-impl Extensions {
-    fn from_attrs(ron: &ast::Ron) -> Self {
-        let mut extensions = Extensions::default();
-
-        for attribute in &ron.attributes {
-            match &attribute.value {
-                Attribute::Enable(list) => for extension in &list.value {
-                    match extension.value {
-                        Extension::UnwrapNewtypes => {
-                            extensions.unwrap_newtypes = true;
-                        }
-                        Extension::ImplicitSome => {
-                            extensions.implicit_some = true;
-                        }
-                    }
-                }
+    from_str_with_options(s, Options::default())
+}
+
+pub fn from_str_with_options<'a, T>(s: &'a str, options: Options) -> Result<T, crate::error::Error>
+where
+    T: Deserialize<'a>,
+{
+    from_str_at(s, Location { line: 1, column: 1 }, options)
+}
+
+/// Deserializes `s`, a part of a larger document starting at `start`
+pub(crate) fn from_str_at<'a, T>(
+    s: &'a str,
+    start: Location,
+    options: Options,
+) -> Result<T, crate::error::Error>
+where
+    T: Deserialize<'a>,
+{
+    let ron = ast_from_str_at(s, start, options)?;
+
+    from_ron_at(&ron, s, start, options)
+}
+
+/// Deserializes `ron`, which was parsed from `s` starting at `start`
+pub(crate) fn from_ron_at<'a, T>(
+    ron: &ast::Ron<'a>,
+    s: &'a str,
+    start: Location,
+    options: Options,
+) -> Result<T, crate::error::Error>
+where
+    T: Deserialize<'a>,
+{
+    let source = Source::new(s, start);
+
+    T::deserialize(RonDeserializer {
+        context: Context {
+            options: options.with_attrs(ron),
+            depth: 0,
+            warnings: None,
+            source: Some(&source),
+        },
+        expr: &ron.expr,
+    })
+}
+
+/// Like [`from_str_with_options`], but also returns a warning for every struct field
+/// or map entry the target type ignored, e.g. a misspelled optional field.
+///
+/// The warnings point at the ignored `key: value` entries and carry their [`crate::Path`].
+///
+/// ```
+/// use ron_reboot::utf8_parser::serde::{from_str_with_warnings, Options};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(default)]
+///     verbose: bool,
+/// }
+///
+/// let (config, warnings) =
+///     from_str_with_warnings::<Config>("(verbsoe: true)", Options::default()).unwrap();
+///
+/// assert!(!config.verbose);
+/// assert_eq!(
+///     warnings[0].display_with_severity("warning").to_string(),
+///     "warning at 1:2 in `verbsoe`: ignored field `verbsoe`"
+/// );
+///
+/// // Rendered with `ron_reboot::Renderer::new().print_with_severity("warning", &warnings[0])`
+/// ```
+pub fn from_str_with_warnings<'a, T>(
+    s: &'a str,
+    options: Options,
+) -> Result<(T, Vec<crate::error::Error>), crate::error::Error>
+where
+    T: Deserialize<'a>,
+{
+    let ron = ast_from_str_at(s, Location { line: 1, column: 1 }, options)?;
+    let warnings = RefCell::new(Warnings::default());
+    let source = Source::new(s, Location { line: 1, column: 1 });
+
+    let value = T::deserialize(RonDeserializer {
+        context: Context {
+            options: options.with_attrs(&ron),
+            depth: 0,
+            warnings: Some(&warnings),
+            source: Some(&source),
+        },
+        expr: &ron.expr,
+    })?;
+
+    Ok((value, warnings.into_inner().warnings))
+}
+
+/// How `deserialize_any` exposes tagged expressions (`Name`, `Name(..)`, `Name(a: ..)`),
+/// see [`Options::tagged`]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TaggedMode {
+    /// Drop the name; structs become maps, tuples sequences and units strings
+    #[default]
+    Ignore,
+    /// Call `visit_enum` with the name as variant
+    Enum,
+    /// Single-entry map from the name to the content, like serde's externally tagged enums.
+    ///
+    /// Tuples with one element map to the element itself, units stay strings.
+    /// This is understood by `#[serde(untagged)]` enums containing normal enums.
+    Map,
+    /// Insert the name as field, so `Circle(r: 1.0)` reads as `(type: "Circle", r: 1.0)`
+    /// for `TaggedMode::Field("type")`; units become structs with just that field.
+    ///
+    /// This is what `#[serde(tag = "type")]` enums expect.
+    Field(&'static str),
+    /// Split into the name and the content, so `Circle(r: 1.0)` reads as
+    /// `(type: "Circle", data: (r: 1.0))` for `TaggedMode::Adjacent("type", "data")`;
+    /// units have no content, like in serde.
+    ///
+    /// This is what `#[serde(tag = "type", content = "data")]` enums expect.
+    Adjacent(&'static str, &'static str),
+}
+
+/// The options and the nesting depth of the expression being deserialized
+#[derive(Clone, Copy, Debug)]
+struct Context<'a, 'de> {
+    options: Options,
+    depth: usize,
+    /// Only recorded for [`from_str_with_warnings`]
+    warnings: Option<&'a RefCell<Warnings>>,
+    /// The document being deserialized, needed for `RawRon`
+    source: Option<&'a Source<'de>>,
+}
+
+#[derive(Debug, Default)]
+struct Warnings {
+    warnings: Vec<Error>,
+    /// Path segments & spans of the entries containing the expression being deserialized
+    path: Vec<(PathSegment, (Location, Location))>,
+}
+
+impl<'a, 'de> Context<'a, 'de> {
+    /// The context for the contents of a container,
+    /// erroring if they are nested deeper than the recursion limit
+    ///
+    /// Like `Input::nested` in the parser, the depth of an expression is the number of
+    /// containers around it, so the contents of a container at `limit` exceed the limit.
+    fn nested(self) -> Result<Self, Error> {
+        let depth = self.depth + 1;
+
+        match self.options.recursion_limit {
+            Some(limit) if depth > limit => Err(Error {
+                kind: ErrorKind::RecursionLimitExceeded(limit),
+                context: None,
+            }),
+            _ => Ok(Context { depth, ..self }),
+        }
+    }
+
+    /// Runs `f`, which deserializes the value of the entry (field, element, ..) spanning `entry`,
+    /// adding `segment` to the path of errors and warnings from within it
+    fn in_path<T>(
+        self,
+        segment: impl Fn() -> PathSegment,
+        entry: (Location, Location),
+        f: impl FnOnce() -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        if let Some(warnings) = self.warnings {
+            warnings.borrow_mut().path.push((segment(), entry));
+        }
+
+        let res = f();
+
+        if let Some(warnings) = self.warnings {
+            warnings.borrow_mut().path.pop();
+        }
+
+        res.map_err(|e| e.context_path(segment()))
+    }
+
+    /// Records a warning about the ignored expression spanning `start..end`
+    fn warn_ignored(self, start: Location, end: Location) {
+        let mut warnings = match self.warnings {
+            Some(warnings) => warnings.borrow_mut(),
+            None => return,
+        };
+
+        // Point at the whole entry, e.g. `key: value` for a misspelled field
+        let (kind, (start, end)) = match warnings.path.last() {
+            Some((PathSegment::Field(name), entry)) => {
+                (ErrorKind::IgnoredField(name.clone()), *entry)
+            }
+            Some((_, entry)) => (ErrorKind::IgnoredValue, *entry),
+            None => (ErrorKind::IgnoredValue, (start, end)),
+        };
+        let warning = warnings.path.iter().rev().fold(
+            Error {
+                kind,
+                context: None,
             }
+            .context_loc(start, end),
+            |e, (segment, _)| e.context_path(segment.clone()),
+        );
+
+        warnings.warnings.push(warning);
+    }
+
+    fn check_duplicate_fields(self, s: &ast::Struct) -> Result<(), Error> {
+        if self.options.duplicate_keys == DuplicateKeys::Allow {
+            return Ok(());
         }
 
-        extensions
+        match s.duplicate_fields().first() {
+            Some((first, duplicate)) => Err(Error {
+                kind: ErrorKind::DuplicateField(duplicate.value.0.to_owned()),
+                context: None,
+            }
+            .context_loc(duplicate.start, duplicate.end)
+            .context_label(first.start, first.end, "first defined here")),
+            None => Ok(()),
+        }
+    }
+
+    /// Errors if `s`, the content of `t`, has a field `tag` although the name is used as tag
+    fn check_tag_field(self, tag: &str, t: &ast::Tagged, s: &ast::Struct) -> Result<(), Error> {
+        if self.options.duplicate_keys == DuplicateKeys::Allow {
+            return Ok(());
+        }
+
+        match s.fields.iter().find(|kv| kv.value.key.value.0 == tag) {
+            Some(kv) => Err(Error {
+                kind: ErrorKind::DuplicateField(tag.to_owned()),
+                context: None,
+            }
+            .context_loc(kv.value.key.start, kv.value.key.end)
+            .context_label(t.ident.start, t.ident.end, "already given by the name")),
+            None => Ok(()),
+        }
+    }
+
+    fn check_duplicate_keys(self, m: &ast::Map) -> Result<(), Error> {
+        if self.options.duplicate_keys == DuplicateKeys::Allow {
+            return Ok(());
+        }
+
+        match m.duplicate_keys().first() {
+            Some((first, duplicate)) => Err(Error {
+                kind: ErrorKind::DuplicateKey,
+                context: None,
+            }
+            .context_loc(duplicate.start, duplicate.end)
+            .context_label(first.start, first.end, "first defined here")),
+            None => Ok(()),
+        }
     }
 }
 
+macro_rules! deserialize_integer {
+    ($($method:ident: $ty:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.deserialize_integer(
+                    visitor,
+                    ($ty::MIN as f64, $ty::MAX as f64),
+                    stringify!($ty),
+                )
+            }
+        )*
+    };
+}
+
 pub struct RonDeserializer<'a, 'de> {
-    extensions: Extensions,
-    expr: &'a mut ast::Spanned<ast::Expr<'de>>,
+    context: Context<'a, 'de>,
+    expr: &'a ast::Spanned<ast::Expr<'de>>,
 }
 
 impl<'a, 'de> RonDeserializer<'a, 'de> {
     /// Create a deserializer from a ron ast
     ///
-    /// The ast will be completely replaced with empty exprs,
-    /// thus cannot be used anymore.
-    pub fn from_ron(ron: &'a mut ast::Ron<'de>) -> Self {
+    /// The ast is left unchanged, so it can be deserialized again (e.g. into another type)
+    /// or linted afterwards. Strings are borrowed from it and only copied if the visitor
+    /// needs an owned string.
+    pub fn from_ron(ron: &'a ast::Ron<'de>) -> Self {
+        Self::from_ron_with_options(ron, Options::default())
+    }
+
+    /// Like [`RonDeserializer::from_ron`], extensions enabled in the file
+    /// are applied on top of `options`.
+    pub fn from_ron_with_options(ron: &'a ast::Ron<'de>, options: Options) -> Self {
         RonDeserializer {
-            extensions: Extensions::from_attrs(ron),
-            expr: &mut ron.expr,
+            context: Context {
+                options: options.with_attrs(ron),
+                depth: 0,
+                warnings: None,
+                source: None,
+            },
+            expr: &ron.expr,
         }
     }
 
@@ -93,49 +353,103 @@ impl<'a, 'de> Deserializer<'de> for RonDeserializer<'a, 'de> {
     where
         V: Visitor<'de>,
     {
-        let res = match self.expr.value.take() {
+        let res = match &self.expr.value {
             Unit => visitor.visit_unit(),
-            Optional(Some(mut o)) => visitor.visit_some(RonDeserializer { extensions: self.extensions, expr: &mut *o }),
-            Optional(None) => visitor.visit_none(),
-            Bool(b) => visitor.visit_bool(b),
-            Tuple(mut t) => visitor.visit_seq(SeqDeserializer {
-                extensions: self.extensions,
-                iter: t.elements.iter_mut(),
-            }),
-            List(mut l) => visitor.visit_seq(SeqDeserializer {
-                extensions: self.extensions,
-                iter: l.elements.iter_mut(),
+            Optional(Some(o)) => visitor.visit_some(RonDeserializer {
+                context: self.context.nested()?,
+                expr: o,
             }),
-            Map(mut m) => visitor.visit_map(MapDeserializer {
-                extensions: self.extensions,
-                iter: m.entries.iter_mut(),
-                value: None,
+            Optional(None) => visitor.visit_none(),
+            Bool(b) => visitor.visit_bool(*b),
+            Tuple(t) => visitor.visit_seq(SeqDeserializer {
+                context: self.context.nested()?,
+                iter: t.elements.iter(),
+                index: 0,
             }),
-            Struct(mut s) => visitor.visit_map(StructDeserializer {
-                extensions: self.extensions,
-                iter: s.fields.iter_mut(),
-                value: None,
+            List(l) => visitor.visit_seq(SeqDeserializer {
+                context: self.context.nested()?,
+                iter: l.elements.iter(),
+                index: 0,
             }),
+            Map(m) => visitor.visit_map(MapDeserializer::new(self.context, m)?),
+            Struct(s) => visitor.visit_map(StructDeserializer::new(self.context, s, &[])?),
             Integer(i) => match i {
-                Integer::Signed(s) => visitor.visit_i64(s.into()),
-                Integer::Unsigned(u) => visitor.visit_u64(u.into()),
+                Integer::Signed(s) => visitor.visit_i64(s.clone().into()),
+                Integer::Unsigned(u) => visitor.visit_u64((*u).into()),
             },
             Str(s) => visitor.visit_borrowed_str(s),
-            String(s) => visitor.visit_string(s),
-            Decimal(d) => visitor.visit_f64(d.into()),
-            // TODO: deserialize as enum?
-            Tagged(t) => match t.untagged.value {
-                Untagged::Struct(mut s) => visitor.visit_map(StructDeserializer {
-                    extensions: self.extensions,
-                    iter: s.fields.iter_mut(),
-                    value: None,
-                }),
-                Untagged::Tuple(mut t) => visitor.visit_seq(SeqDeserializer {
-                    extensions: self.extensions,
-                    iter: t.elements.iter_mut(),
+            String(s) => visitor.visit_str(s),
+            Char(c) => visitor.visit_char(*c),
+            Decimal(d) => visitor.visit_f64(d.clone().into()),
+            NonFinite(n) => visitor.visit_f64((*n).into()),
+            UnquotedKey(k) => visitor.visit_borrowed_str(k.0),
+            Tagged(t) if self.context.options.tagged == TaggedMode::Enum => {
+                visitor.visit_enum(EnumDeserializer {
+                    context: self.context,
+                    tagged: t,
+                    variants: &[],
+                })
+            }
+            Tagged(t)
+                if self.context.options.tagged == TaggedMode::Map
+                    && t.untagged.value != Untagged::Unit =>
+            {
+                visitor.visit_map(StructDeserializer::tagged(
+                    self.context,
+                    vec![externally_tagged(t)],
+                    &[],
+                    &[],
+                )?)
+            }
+            Tagged(t)
+                if matches!(t.untagged.value, Untagged::Struct(_) | Untagged::Unit)
+                    && matches!(self.context.options.tagged, TaggedMode::Field(_)) =>
+            {
+                let tag = match self.context.options.tagged {
+                    TaggedMode::Field(tag) => tag,
+                    _ => unreachable!(),
+                };
+
+                let fields = match &t.untagged.value {
+                    Untagged::Struct(s) => {
+                        self.context.check_duplicate_fields(s)?;
+                        self.context.check_tag_field(tag, t, s)?;
+
+                        &s.fields[..]
+                    }
+                    _ => &[],
+                };
+
+                visitor.visit_map(StructDeserializer::tagged(
+                    self.context,
+                    vec![tag_entry(tag, t)],
+                    fields,
+                    &[],
+                )?)
+            }
+            Tagged(t) if matches!(self.context.options.tagged, TaggedMode::Adjacent(..)) => {
+                let (tag, content) = match self.context.options.tagged {
+                    TaggedMode::Adjacent(tag, content) => (tag, content),
+                    _ => unreachable!(),
+                };
+
+                visitor.visit_map(StructDeserializer::tagged(
+                    self.context,
+                    adjacently_tagged(tag, content, t),
+                    &[],
+                    &[],
+                )?)
+            }
+            Tagged(t) => match &t.untagged.value {
+                Untagged::Struct(s) => {
+                    visitor.visit_map(StructDeserializer::new(self.context, s, &[])?)
+                }
+                Untagged::Tuple(t) => visitor.visit_seq(SeqDeserializer {
+                    context: self.context.nested()?,
+                    iter: t.elements.iter(),
+                    index: 0,
                 }),
                 Untagged::Unit => visitor.visit_borrowed_str(t.ident.value.0),
-                //_ => visitor.visit_enum(EnumDeserializer { tagged: &mut t }),
             },
         };
 
@@ -145,7 +459,7 @@ impl<'a, 'de> Deserializer<'de> for RonDeserializer<'a, 'de> {
     fn deserialize_struct<V>(
         self,
         name: &'static str,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
@@ -153,8 +467,42 @@ impl<'a, 'de> Deserializer<'de> for RonDeserializer<'a, 'de> {
     {
         let start_loc = self.expr.start;
         let end_loc = self.expr.end;
-        let res = match self.expr.value.take() {
-            ast::Expr::Tagged(ast::Tagged { ident, .. }) if ident.value.0 != name => {
+
+        if name == spanned::NAME && fields == spanned::FIELDS {
+            return visitor.visit_map(SpannedDeserializer::new(start_loc, end_loc, self));
+        }
+        if name == raw::NAME && fields == spanned::FIELDS {
+            let source = self.context.source.ok_or_else(|| {
+                Error::custom("`RawRon` can only be deserialized from a string")
+                    .context_loc(start_loc, end_loc)
+            })?;
+            let raw = source.slice(start_loc, end_loc);
+
+            return visitor.visit_map(SpannedDeserializer::new(
+                start_loc,
+                end_loc,
+                BorrowedStrDeserializer::new(raw),
+            ));
+        }
+
+        let res = match &self.expr.value {
+            // adjacently tagged enums are deserialized as struct with the fields `[tag, content]`
+            ast::Expr::Tagged(t)
+                if t.ident.value.0 != name
+                    && fields.len() == 2
+                    && self.context.options.tagged
+                        == TaggedMode::Adjacent(fields[0], fields[1]) =>
+            {
+                visitor.visit_map(StructDeserializer::tagged(
+                    self.context,
+                    adjacently_tagged(fields[0], fields[1], t),
+                    &[],
+                    fields,
+                )?)
+            }
+            ast::Expr::Tagged(ast::Tagged { ident, .. })
+                if ident.value.0 != name && self.context.options.strict_struct_names =>
+            {
                 Err(Error::custom(format!(
                     "invalid struct type: `{}`, expected `{}`",
                     ident.value.0, name
@@ -164,21 +512,16 @@ impl<'a, 'de> Deserializer<'de> for RonDeserializer<'a, 'de> {
             ast::Expr::Tagged(ast::Tagged {
                 untagged:
                     ast::Spanned {
-                        value: Untagged::Struct(mut s),
-                        ..
+                        start,
+                        value: Untagged::Struct(s),
+                        end,
                     },
                 ..
-            })
-            | ast::Expr::Struct(mut s) => visitor.visit_map(StructDeserializer {
-                extensions: self.extensions,
-                iter: s.fields.iter_mut(),
-                value: None,
-            }),
-            x => {
-                self.expr.value = x;
-
-                self.deserialize_any(visitor)
-            },
+            }) => visit_struct(self.context, s, (*start, *end), fields, visitor),
+            ast::Expr::Struct(s) => {
+                visit_struct(self.context, s, (start_loc, end_loc), fields, visitor)
+            }
+            _ => self.deserialize_any(visitor),
         };
 
         res.map_err(|e| e.context_loc(start_loc, end_loc))
@@ -187,7 +530,7 @@ impl<'a, 'de> Deserializer<'de> for RonDeserializer<'a, 'de> {
     fn deserialize_enum<V>(
         self,
         _name: &'static str,
-        _variants: &'static [&'static str],
+        variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
@@ -195,14 +538,16 @@ impl<'a, 'de> Deserializer<'de> for RonDeserializer<'a, 'de> {
     {
         let start_loc = self.expr.start;
         let end_loc = self.expr.end;
-        let res = match self.expr.value.take() {
-            Tagged(mut t) => visitor.visit_enum(EnumDeserializer { extensions: self.extensions, tagged: &mut t }),
+        let res = match &self.expr.value {
+            Tagged(t) => visitor.visit_enum(EnumDeserializer {
+                context: self.context,
+                tagged: t,
+                variants,
+            }),
+            // `{Variant: ..}` with the `unquoted_map_keys` extension
+            UnquotedKey(k) => visitor.visit_enum(BorrowedStrDeserializer::new(k.0)),
             // probably no enum and will error
-            x => {
-                self.expr.value = x;
-
-                self.deserialize_any(visitor)
-            },
+            _ => self.deserialize_any(visitor),
         };
 
         res.map_err(|e| e.context_loc(start_loc, end_loc))
@@ -220,78 +565,124 @@ impl<'a, 'de> Deserializer<'de> for RonDeserializer<'a, 'de> {
     where
         V: Visitor<'de>,
     {
-        self.expr.value.take();
+        self.context.warn_ignored(self.expr.start, self.expr.end);
 
         visitor.visit_unit()
     }
 
-    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
-        match self.expr.value.take() {
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match &self.expr.value {
             Optional(None) => visitor.visit_none(),
-            Optional(Some(mut e)) => visitor.visit_some(RonDeserializer {
-                extensions: self.extensions,
-                expr: &mut e,
+            Optional(Some(e)) => visitor.visit_some(RonDeserializer {
+                context: self.context.nested()?,
+                expr: e,
             }),
-            x => {
-                self.expr.value = x;
-
-                let de = RonDeserializer {
-                    extensions: self.extensions,
-                    expr: self.expr,
-                };
-
-                if self.extensions.implicit_some {
-                    visitor.visit_some(de)
-                } else {
-                    de.deserialize_any(visitor)
-                }
-            }
+            _ if self.context.options.implicit_some => visitor.visit_some(self),
+            _ => self.deserialize_any(visitor),
         }
     }
 
-    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
-        match self.expr.value.take() {
-            ast::Expr::Tagged(ast::Tagged { ident, .. }) if ident.value.0 != name => {
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match &self.expr.value {
+            ast::Expr::Tagged(ast::Tagged { ident, .. })
+                if ident.value.0 != name && self.context.options.strict_struct_names =>
+            {
                 Err(Error::custom(format!(
                     "invalid newtype struct type: `{}`, expected `{}`",
                     ident.value.0, name
                 ))
-                    .context_loc(ident.start, ident.end))
+                .context_loc(ident.start, ident.end))
             }
             ast::Expr::Tagged(ast::Tagged {
-                                  untagged:
-                                  ast::Spanned {
-                                      value: Untagged::Tuple(mut t),
-                                      ..
-                                  },
-                                  ..
-                              })
-            | ast::Expr::Tuple(mut t) if t.elements.len() == 1 => visitor.visit_newtype_struct(RonDeserializer {
-                extensions: self.extensions,
-                expr: t.elements.iter_mut().next().unwrap(),
-            }),
-            x => {
-                self.expr.value = x;
-
-                if self.extensions.unwrap_newtypes {
-                    visitor.visit_newtype_struct(self)
-                } else {
-                    self.deserialize_any(visitor)
-                }
-            },
+                untagged:
+                    ast::Spanned {
+                        value: Untagged::Tuple(t),
+                        ..
+                    },
+                ..
+            })
+            | ast::Expr::Tuple(t)
+                if t.elements.len() == 1 =>
+            {
+                visitor.visit_newtype_struct(RonDeserializer {
+                    context: self.context.nested()?,
+                    expr: &t.elements[0],
+                })
+            }
+            _ if self.context.options.unwrap_newtypes => visitor.visit_newtype_struct(self),
+            _ => self.deserialize_any(visitor),
         }
     }
 
+    deserialize_integer! {
+        deserialize_i8: i8 deserialize_i16: i16 deserialize_i32: i32 deserialize_i64: i64
+        deserialize_i128: i128
+        deserialize_u8: u8 deserialize_u16: u16 deserialize_u32: u32 deserialize_u64: u64
+        deserialize_u128: u128
+    }
+
     forward_to_deserialize_any! {
-        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char string str
+        bool f32 f64 char string str
         bytes byte_buf unit unit_struct seq tuple
         tuple_struct map
     }
 }
 
+impl<'a, 'de> RonDeserializer<'a, 'de> {
+    /// Like `deserialize_any`, but visits decimals without fractional part as integers
+    /// if [`Options::float_int_coercion`] is enabled
+    ///
+    /// `(min, max)` is the range of the integer type named `expected`.
+    fn deserialize_integer<V>(
+        self,
+        visitor: V,
+        (min, max): (f64, f64),
+        expected: &'static str,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let float = match &self.expr.value {
+            Decimal(d) if self.context.options.float_int_coercion => f64::from(d.clone()),
+            _ => return self.deserialize_any(visitor),
+        };
+
+        if float.fract() != 0.0 || !float.is_finite() {
+            return self.deserialize_any(visitor);
+        }
+
+        // `max as f64` may be rounded up to the next power of two, which is out of range as well
+        if float < min || float >= max + 1.0 {
+            return Err(Error::invalid_value(Unexpected::Float(float), &expected)
+                .context_loc(self.expr.start, self.expr.end));
+        }
+
+        let res = match float < 0.0 {
+            true if float >= i64::MIN as f64 => visitor.visit_i64(float as i64),
+            true => visitor.visit_i128(float as i128),
+            false if float < u64::MAX as f64 => visitor.visit_u64(float as u64),
+            false => visitor.visit_u128(float as u128),
+        };
+
+        res.map_err(|e: Error| e.context_loc(self.expr.start, self.expr.end))
+    }
+}
+
 struct SeqDeserializer<'a, 'de> {
-    extensions: Extensions,
-    iter: std::slice::IterMut<'a, ast::Spanned<ast::Expr<'de>>>,
+    context: Context<'a, 'de>,
+    iter: std::slice::Iter<'a, ast::Spanned<ast::Expr<'de>>>,
+    /// Index of the next element
+    index: usize,
 }
 
 impl<'a, 'de> SeqAccess<'de> for SeqDeserializer<'a, 'de> {
@@ -301,20 +692,69 @@ impl<'a, 'de> SeqAccess<'de> for SeqDeserializer<'a, 'de> {
     where
         T: DeserializeSeed<'de>,
     {
+        let index = self.index;
+        self.index += 1;
+
         match self.iter.next() {
-            Some(x) => seed
-                .deserialize(RonDeserializer { extensions: self.extensions, expr: x })
-                .map(Some)
-                .map_err(|e| e.context_loc(x.start.into(), x.end.into())),
+            Some(x) => {
+                let entry = (x.start, x.end);
+
+                self.context
+                    .in_path(
+                        || PathSegment::Index(index),
+                        entry,
+                        || {
+                            seed.deserialize(RonDeserializer {
+                                context: self.context,
+                                expr: x,
+                            })
+                        },
+                    )
+                    .map(Some)
+                    .map_err(|e| e.context_loc(entry.0, entry.1))
+            }
             None => Ok(None),
         }
     }
 }
 
 struct StructDeserializer<'a, 'de> {
-    extensions: Extensions,
-    iter: std::slice::IterMut<'a, ast::Spanned<ast::KeyValue<'de, ast::Ident<'de>>>>,
-    value: Option<&'a mut ast::Spanned<ast::Expr<'de>>>,
+    context: Context<'a, 'de>,
+    /// The fields of the Rust struct, empty if unknown
+    fields: &'static [&'static str],
+    /// Entries made up from the name of a tagged expression, visited before `iter`
+    tags: std::vec::IntoIter<TagEntry<'a, 'de>>,
+    iter: std::slice::Iter<'a, ast::Spanned<ast::KeyValue<'de, ast::Ident<'de>>>>,
+    /// The field, span & value of the entry whose key was visited last
+    value: Option<(&'de str, (Location, Location), EntryValue<'a, 'de>)>,
+}
+
+impl<'a, 'de> StructDeserializer<'a, 'de> {
+    fn new(
+        context: Context<'a, 'de>,
+        s: &'a ast::Struct<'de>,
+        fields: &'static [&'static str],
+    ) -> Result<Self, Error> {
+        context.check_duplicate_fields(s)?;
+
+        Self::tagged(context, vec![], &s.fields, fields)
+    }
+
+    /// Visits `tags` followed by the struct fields `entries`
+    fn tagged(
+        context: Context<'a, 'de>,
+        tags: Vec<TagEntry<'a, 'de>>,
+        entries: &'a [ast::Spanned<ast::KeyValue<'de, ast::Ident<'de>>>],
+        fields: &'static [&'static str],
+    ) -> Result<Self, Error> {
+        Ok(StructDeserializer {
+            context: context.nested()?,
+            fields,
+            tags: tags.into_iter(),
+            iter: entries.iter(),
+            value: None,
+        })
+    }
 }
 
 impl<'a, 'de> MapAccess<'de> for StructDeserializer<'a, 'de> {
@@ -324,16 +764,27 @@ impl<'a, 'de> MapAccess<'de> for StructDeserializer<'a, 'de> {
     where
         K: DeserializeSeed<'de>,
     {
+        if let Some(tag) = self.tags.next() {
+            self.value = Some((tag.key, tag.entry, tag.value));
+
+            return seed
+                .deserialize(BorrowedStrDeserializer::new(tag.key))
+                .map(Some)
+                .map_err(|e: Error| e.context_loc(tag.entry.0, tag.entry.1));
+        }
+
         match self.iter.next() {
             Some(x) => {
                 let start_loc = x.start;
                 let end_loc = x.end;
-                self.value = Some(&mut x.value.value);
+                let key = x.value.key.value.0;
+                self.value = Some((key, (start_loc, end_loc), EntryValue::Expr(&x.value.value)));
 
                 seed.deserialize(IdentDeserializer {
-                    ident: &mut x.value.key,
+                    ident: &x.value.key,
                 })
                 .map(Some)
+                .map_err(|e| suggest_name(e, "field", key, self.fields))
                 .map_err(|e| e.context_loc(start_loc, end_loc))
             }
             None => Ok(None),
@@ -344,12 +795,19 @@ impl<'a, 'de> MapAccess<'de> for StructDeserializer<'a, 'de> {
     where
         V: DeserializeSeed<'de>,
     {
-        let x = self
+        let (field, entry, value) = self
             .value
             .take()
             .expect("called next_value_seed before next_key_seed");
-        seed.deserialize(RonDeserializer { extensions: self.extensions, expr: x })
-            .map_err(|e| e.context_loc(x.start.into(), x.end.into()))
+        let (start, end) = value.span();
+
+        self.context
+            .in_path(
+                || PathSegment::Field(field.to_owned()),
+                entry,
+                || value.deserialize(self.context, seed),
+            )
+            .map_err(|e| e.context_loc(start, end))
     }
 
     fn next_entry_seed<K, V>(
@@ -361,17 +819,32 @@ impl<'a, 'de> MapAccess<'de> for StructDeserializer<'a, 'de> {
         K: DeserializeSeed<'de>,
         V: DeserializeSeed<'de>,
     {
+        if self.tags.len() != 0 {
+            return match self.next_key_seed(kseed)? {
+                Some(key) => Ok(Some((key, self.next_value_seed(vseed)?))),
+                None => Ok(None),
+            };
+        }
+
         match self.iter.next() {
             Some(x) => {
+                let name = x.value.key.value.0;
                 let key = kseed
                     .deserialize(IdentDeserializer {
-                        ident: &mut x.value.key,
+                        ident: &x.value.key,
                     })
+                    .map_err(|e| suggest_name(e, "field", name, self.fields))
                     .map_err(|e| e.context_loc(x.start.into(), x.end.into()))?;
-                let value = vseed.deserialize(RonDeserializer {
-                    extensions: self.extensions,
-                    expr: &mut x.value.value,
-                })?;
+                let value = self.context.in_path(
+                    || PathSegment::Field(name.to_owned()),
+                    (x.start, x.end),
+                    || {
+                        vseed.deserialize(RonDeserializer {
+                            context: self.context,
+                            expr: &x.value.value,
+                        })
+                    },
+                )?;
 
                 Ok(Some((key, value)))
             }
@@ -380,14 +853,77 @@ impl<'a, 'de> MapAccess<'de> for StructDeserializer<'a, 'de> {
     }
 
     fn size_hint(&self) -> Option<usize> {
-        Some(self.iter.size_hint().0)
+        Some(self.tags.len() + self.iter.size_hint().0)
+    }
+}
+
+/// An entry made up from the name of a tagged expression, see [`TaggedMode`]
+struct TagEntry<'a, 'de> {
+    key: &'de str,
+    /// The span of the whole entry, which is also used for the key
+    entry: (Location, Location),
+    value: EntryValue<'a, 'de>,
+}
+
+/// The value of a struct entry, borrowed from the ast
+#[derive(Clone, Copy)]
+enum EntryValue<'a, 'de> {
+    Expr(&'a ast::Spanned<ast::Expr<'de>>),
+    /// The name of a tagged expression, as string or unit variant
+    Variant(&'a ast::Spanned<ast::Ident<'de>>),
+    /// The content of a tagged expression, see [`ContentDeserializer`]
+    Content(&'a ast::Spanned<Untagged<'de>>),
+}
+
+impl<'a, 'de> EntryValue<'a, 'de> {
+    /// The content of a tagged expression,
+    /// unwrapping one element tuples like serde's newtype variants
+    fn content(untagged: &'a ast::Spanned<Untagged<'de>>) -> Self {
+        match &untagged.value {
+            Untagged::Tuple(t) if t.elements.len() == 1 => EntryValue::Expr(&t.elements[0]),
+            _ => EntryValue::Content(untagged),
+        }
+    }
+
+    fn span(self) -> (Location, Location) {
+        match self {
+            EntryValue::Expr(expr) => (expr.start, expr.end),
+            EntryValue::Variant(ident) => (ident.start, ident.end),
+            EntryValue::Content(untagged) => (untagged.start, untagged.end),
+        }
+    }
+
+    fn deserialize<T>(self, context: Context<'a, 'de>, seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self {
+            EntryValue::Expr(expr) => seed.deserialize(RonDeserializer { context, expr }),
+            EntryValue::Variant(ident) => seed.deserialize(IdentDeserializer { ident }),
+            EntryValue::Content(untagged) => {
+                seed.deserialize(ContentDeserializer { context, untagged })
+            }
+        }
     }
 }
 
 struct MapDeserializer<'a, 'de> {
-    extensions: Extensions,
-    iter: std::slice::IterMut<'a, ast::Spanned<ast::KeyValue<'de, ast::Expr<'de>>>>,
-    value: Option<&'a mut ast::Spanned<ast::Expr<'de>>>,
+    context: Context<'a, 'de>,
+    iter: std::slice::Iter<'a, ast::Spanned<ast::KeyValue<'de, ast::Expr<'de>>>>,
+    /// The entry whose key was visited last
+    value: Option<&'a ast::Spanned<ast::KeyValue<'de, ast::Expr<'de>>>>,
+}
+
+impl<'a, 'de> MapDeserializer<'a, 'de> {
+    fn new(context: Context<'a, 'de>, m: &'a ast::Map<'de>) -> Result<Self, Error> {
+        context.check_duplicate_keys(m)?;
+
+        Ok(MapDeserializer {
+            context: context.nested()?,
+            iter: m.entries.iter(),
+            value: None,
+        })
+    }
 }
 
 impl<'a, 'de> MapAccess<'de> for MapDeserializer<'a, 'de> {
@@ -402,11 +938,11 @@ impl<'a, 'de> MapAccess<'de> for MapDeserializer<'a, 'de> {
                 let start_loc = x.start;
                 let end_loc = x.end;
 
-                self.value = Some(&mut x.value.value);
+                self.value = Some(x);
 
                 seed.deserialize(RonDeserializer {
-                    extensions: self.extensions,
-                    expr: &mut x.value.key,
+                    context: self.context,
+                    expr: &x.value.key,
                 })
                 .map(Some)
                 .map_err(|e| e.context_loc(start_loc, end_loc))
@@ -419,12 +955,25 @@ impl<'a, 'de> MapAccess<'de> for MapDeserializer<'a, 'de> {
     where
         V: DeserializeSeed<'de>,
     {
-        let x = self
+        let entry = self
             .value
             .take()
             .expect("called next_value_seed before next_key_seed");
-        seed.deserialize(RonDeserializer { extensions: self.extensions, expr: x })
-            .map_err(|e| e.context_loc(x.start.into(), x.end.into()))
+        let x = &entry.value.value;
+        let (start, end) = (x.start, x.end);
+
+        self.context
+            .in_path(
+                || key_segment(&entry.value.key.value),
+                (entry.start, entry.end),
+                || {
+                    seed.deserialize(RonDeserializer {
+                        context: self.context,
+                        expr: x,
+                    })
+                },
+            )
+            .map_err(|e| e.context_loc(start, end))
     }
 
     fn next_entry_seed<K, V>(
@@ -440,16 +989,24 @@ impl<'a, 'de> MapAccess<'de> for MapDeserializer<'a, 'de> {
             Some(x) => {
                 let key = kseed
                     .deserialize(RonDeserializer {
-                        extensions: self.extensions,
-                        expr: &mut x.value.key,
-                    })
-                    .map_err(|e| e.context_loc(x.start.into(), x.end.into()))?;
-                let value = vseed
-                    .deserialize(RonDeserializer {
-                        extensions: self.extensions,
-                        expr: &mut x.value.value,
+                        context: self.context,
+                        expr: &x.value.key,
                     })
                     .map_err(|e| e.context_loc(x.start.into(), x.end.into()))?;
+                let entry = (x.start, x.end);
+                let value = self
+                    .context
+                    .in_path(
+                        || key_segment(&x.value.key.value),
+                        entry,
+                        || {
+                            vseed.deserialize(RonDeserializer {
+                                context: self.context,
+                                expr: &x.value.value,
+                            })
+                        },
+                    )
+                    .map_err(|e| e.context_loc(entry.0, entry.1))?;
 
                 Ok(Some((key, value)))
             }
@@ -462,8 +1019,159 @@ impl<'a, 'de> MapAccess<'de> for MapDeserializer<'a, 'de> {
     }
 }
 
+/// The path segment of a map entry with `key`, `..` for keys not worth printing
+fn key_segment(key: &ast::Expr) -> PathSegment {
+    PathSegment::Key(match key {
+        Str(s) => format!("{:?}", s),
+        UnquotedKey(k) => format!("{:?}", k.0),
+        String(s) => format!("{:?}", s),
+        Char(c) => format!("{:?}", c),
+        Bool(b) => b.to_string(),
+        Integer(Integer::Signed(i)) => i64::from(i.clone()).to_string(),
+        Integer(Integer::Unsigned(u)) => u64::from(*u).to_string(),
+        Decimal(d) => f64::from(d.clone()).to_string(),
+        NonFinite(n) => f64::from(*n).to_string(),
+        Tagged(ast::Tagged {
+            ident,
+            untagged:
+                ast::Spanned {
+                    value: Untagged::Unit,
+                    ..
+                },
+        }) => ident.value.0.to_owned(),
+        _ => "..".to_owned(),
+    })
+}
+
+/// `Name(..)` as `{ "Name": .. }`, see [`TaggedMode::Map`]
+fn externally_tagged<'a, 'de>(t: &'a ast::Tagged<'de>) -> TagEntry<'a, 'de> {
+    TagEntry {
+        key: t.ident.value.0,
+        entry: (t.ident.start, t.untagged.end),
+        value: EntryValue::content(&t.untagged),
+    }
+}
+
+/// The field `tag` containing the name of `t`, located at the name
+///
+/// Prepended to the fields of `Name(a: ..)` for `(tag: "Name", a: ..)`, see [`TaggedMode::Field`]
+fn tag_entry<'a, 'de>(tag: &'static str, t: &'a ast::Tagged<'de>) -> TagEntry<'a, 'de> {
+    TagEntry {
+        key: tag,
+        entry: (t.ident.start, t.ident.end),
+        value: EntryValue::Variant(&t.ident),
+    }
+}
+
+/// `Name(..)` as `(tag: Name, content: ..)`, omitting `content` for units,
+/// see [`TaggedMode::Adjacent`]
+///
+/// serde deserializes the tag as unit variant, not as string.
+fn adjacently_tagged<'a, 'de>(
+    tag: &'static str,
+    content: &'static str,
+    t: &'a ast::Tagged<'de>,
+) -> Vec<TagEntry<'a, 'de>> {
+    let mut entries = vec![tag_entry(tag, t)];
+
+    if t.untagged.value != Untagged::Unit {
+        entries.push(TagEntry {
+            key: content,
+            entry: (t.untagged.start, t.untagged.end),
+            value: EntryValue::content(&t.untagged),
+        });
+    }
+
+    entries
+}
+
+/// Helps with errors about `name` not being one of `expected`, e.g. a misspelled field
+fn suggest_name(e: Error, what: &str, name: &str, expected: &[&str]) -> Error {
+    if expected.is_empty() || expected.contains(&name) {
+        return e;
+    }
+
+    let e = match similar_name(name, expected) {
+        Some(similar) => e.context_help(format!("did you mean `{}`?", similar)),
+        None => e,
+    };
+
+    e.context_note(format!("valid {}s are {}", what, name_list(expected)))
+}
+
+/// `a`, `b`, `c`
+fn name_list(names: &[&str]) -> std::string::String {
+    names
+        .iter()
+        .map(|name| format!("`{}`", name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Visits the fields of `s`, spanning `start..end`, for a Rust struct with `fields`
+fn visit_struct<'de, V>(
+    context: Context<'_, 'de>,
+    s: &ast::Struct<'de>,
+    (start, end): (Location, Location),
+    fields: &'static [&'static str],
+    visitor: V,
+) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    visitor
+        .visit_map(StructDeserializer::new(context, s, fields)?)
+        .map_err(|e| locate_missing_field(e, s, (start, end), fields))
+}
+
+/// Points `missing field` errors at the closing delimiter of `s`,
+/// which is where the field would be added
+fn locate_missing_field(
+    e: Error,
+    s: &ast::Struct,
+    (start, end): (Location, Location),
+    fields: &[&str],
+) -> Error {
+    let missing = match &e.kind {
+        ErrorKind::MissingField(name) if e.start().is_none() => name.clone(),
+        _ => return e,
+    };
+
+    let closing = Location {
+        line: end.line,
+        column: end.column.saturating_sub(1).max(1),
+    };
+    let opening_end = Location {
+        line: start.line,
+        column: start.column + 1,
+    };
+    let present: Vec<&str> = s.fields.iter().map(|kv| kv.value.key.value.0).collect();
+
+    let e = e
+        .context_loc(closing, end)
+        .context_label(start, opening_end, "struct starts here")
+        .context_note(match present.is_empty() {
+            true => "the struct has no fields".to_owned(),
+            false => format!("present fields are {}", name_list(&present)),
+        });
+
+    // Unknown fields are ignored (without `deny_unknown_fields`), so typos end up here
+    let unknown: Vec<&str> = present
+        .into_iter()
+        .filter(|name| !fields.contains(name))
+        .collect();
+
+    match similar_name(&missing, &unknown) {
+        Some(typo) => e.context_help(format!(
+            "`{}` is not a field, did you mean `{}`?",
+            typo, missing
+        )),
+        None => e,
+    }
+}
+
 struct IdentDeserializer<'a, 'de> {
-    ident: &'a mut ast::Spanned<ast::Ident<'de>>,
+    ident: &'a ast::Spanned<ast::Ident<'de>>,
 }
 
 impl<'a, 'de> Deserializer<'de> for IdentDeserializer<'a, 'de> {
@@ -481,16 +1189,127 @@ impl<'a, 'de> Deserializer<'de> for IdentDeserializer<'a, 'de> {
             .map_err(|e: Error| e.context_loc(start_loc, end_loc))
     }
 
+    /// The identifier as unit variant, e.g. the tag of an adjacently tagged enum
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let name = self.ident.value.0;
+
+        visitor
+            .visit_enum(BorrowedStrDeserializer::new(name))
+            .map_err(|e| suggest_name(e, "variant", name, variants))
+            .map_err(|e: Error| e.context_loc(self.ident.start, self.ident.end))
+    }
+
     forward_to_deserialize_any! {
         bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
         bytes byte_buf option unit unit_struct newtype_struct seq tuple
-        tuple_struct map struct enum identifier ignored_any
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// The content of a tagged expression without its name, e.g. `(a: 1)` of `Name(a: 1)`
+struct ContentDeserializer<'a, 'de> {
+    context: Context<'a, 'de>,
+    untagged: &'a ast::Spanned<Untagged<'de>>,
+}
+
+impl<'a, 'de> Deserializer<'de> for ContentDeserializer<'a, 'de> {
+    type Error = crate::error::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let res = match &self.untagged.value {
+            Untagged::Struct(s) => {
+                visitor.visit_map(StructDeserializer::new(self.context, s, &[])?)
+            }
+            Untagged::Tuple(t) => visitor.visit_seq(SeqDeserializer {
+                context: self.context.nested()?,
+                iter: t.elements.iter(),
+                index: 0,
+            }),
+            Untagged::Unit => visitor.visit_unit(),
+        };
+
+        res.map_err(|e| e.context_loc(self.untagged.start, self.untagged.end))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let span = (self.untagged.start, self.untagged.end);
+
+        if name == spanned::NAME && fields == spanned::FIELDS {
+            return visitor.visit_map(SpannedDeserializer::new(span.0, span.1, self));
+        }
+
+        match &self.untagged.value {
+            Untagged::Struct(s) => visit_struct(self.context, s, span, fields, visitor)
+                .map_err(|e| e.context_loc(span.0, span.1)),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.context.options.implicit_some {
+            true => visitor.visit_some(self),
+            false => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.context.options.unwrap_newtypes {
+            true => visitor.visit_newtype_struct(self),
+            false => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.context
+            .warn_ignored(self.untagged.start, self.untagged.end);
+
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map enum identifier
     }
 }
 
 struct EnumDeserializer<'a, 'de> {
-    extensions: Extensions,
-    tagged: &'a mut ast::Tagged<'de>,
+    context: Context<'a, 'de>,
+    tagged: &'a ast::Tagged<'de>,
+    /// The variants of the Rust enum, empty if unknown
+    variants: &'static [&'static str],
 }
 
 impl<'a, 'de> EnumAccess<'de> for EnumDeserializer<'a, 'de> {
@@ -501,33 +1320,59 @@ impl<'a, 'de> EnumAccess<'de> for EnumDeserializer<'a, 'de> {
     where
         V: DeserializeSeed<'de>,
     {
-        let variant_ident = seed.deserialize(IdentDeserializer {
-            ident: &mut self.tagged.ident,
-        })?;
+        let name = self.tagged.ident.value.0;
+        let variant_ident = seed
+            .deserialize(IdentDeserializer {
+                ident: &self.tagged.ident,
+            })
+            .map_err(|e| suggest_name(e, "variant", name, self.variants))?;
 
         Ok((
             variant_ident,
             UntaggedDeserializer {
-                extensions: self.extensions,
-                untagged: &mut self.tagged.untagged,
+                context: self.context,
+                variant: name,
+                untagged: &self.tagged.untagged,
             },
         ))
     }
 }
 
 struct UntaggedDeserializer<'a, 'de> {
-    extensions: Extensions,
-    untagged: &'a mut ast::Spanned<ast::Untagged<'de>>,
+    context: Context<'a, 'de>,
+    variant: &'de str,
+    untagged: &'a ast::Spanned<ast::Untagged<'de>>,
+}
+
+impl<'a, 'de> UntaggedDeserializer<'a, 'de> {
+    /// Runs `f`, which deserializes the content of the variant
+    fn in_variant<T>(&self, f: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+        let entry = (self.untagged.start, self.untagged.end);
+
+        self.context
+            .in_path(|| PathSegment::Variant(self.variant.to_owned()), entry, f)
+    }
+
+    /// The variant has a different kind than the Rust variant, e.g. a unit for a struct variant
+    fn invalid_variant(&self, expected: &'static str) -> Error {
+        let unexpected = match self.untagged.value {
+            Untagged::Struct(_) => Unexpected::StructVariant,
+            Untagged::Tuple(_) => Unexpected::TupleVariant,
+            Untagged::Unit => Unexpected::UnitVariant,
+        };
+
+        Error::invalid_type(unexpected, &expected)
+            .context_loc(self.untagged.start, self.untagged.end)
+    }
 }
 
 impl<'a, 'de> VariantAccess<'de> for UntaggedDeserializer<'a, 'de> {
     type Error = crate::error::Error;
 
     fn unit_variant(self) -> Result<(), Self::Error> {
-        match self.untagged.value.take() {
-            Untagged::Struct(_) => todo!(),
-            Untagged::Tuple(_) => todo!(),
+        match &self.untagged.value {
             Untagged::Unit => Ok(()),
+            _ => Err(self.invalid_variant("unit variant")),
         }
     }
 
@@ -535,13 +1380,12 @@ impl<'a, 'de> VariantAccess<'de> for UntaggedDeserializer<'a, 'de> {
     where
         T: DeserializeSeed<'de>,
     {
-        match self.untagged.value.take() {
-            Untagged::Struct(_) => todo!(),
-            Untagged::Tuple(mut t) => seed.deserialize(RonDeserializer {
-                extensions: self.extensions,
-                expr: t.elements.iter_mut().next().ok_or_else(|| Error::custom("invalid enum variant, got zero tuple elements, but expected one (newtype variant)"))?
-            }),
-            Untagged::Unit => todo!(),
+        match &self.untagged.value {
+            Untagged::Tuple(t) => self.in_variant(|| seed.deserialize(RonDeserializer {
+                context: self.context.nested()?,
+                expr: t.elements.first().ok_or_else(|| Error::custom("invalid enum variant, got zero tuple elements, but expected one (newtype variant)"))?
+            })),
+            _ => Err(self.invalid_variant("newtype variant")),
         }
     }
 
@@ -549,32 +1393,33 @@ impl<'a, 'de> VariantAccess<'de> for UntaggedDeserializer<'a, 'de> {
     where
         V: Visitor<'de>,
     {
-        match self.untagged.value.take() {
-            Untagged::Struct(_) => todo!(),
-            Untagged::Tuple(mut t) => visitor.visit_seq(SeqDeserializer {
-                extensions: self.extensions,
-                iter: t.elements.iter_mut(),
+        match &self.untagged.value {
+            Untagged::Tuple(t) => self.in_variant(|| {
+                visitor.visit_seq(SeqDeserializer {
+                    context: self.context.nested()?,
+                    iter: t.elements.iter(),
+                    index: 0,
+                })
             }),
-            Untagged::Unit => todo!(),
+            _ => Err(self.invalid_variant("tuple variant")),
         }
     }
 
     fn struct_variant<V>(
         self,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.untagged.value.take() {
-            Untagged::Struct(mut s) => visitor.visit_map(StructDeserializer {
-                extensions: self.extensions,
-                iter: s.fields.iter_mut(),
-                value: None,
-            }),
-            Untagged::Tuple(_) => todo!(),
-            Untagged::Unit => todo!(),
+        let span = (self.untagged.start, self.untagged.end);
+
+        match &self.untagged.value {
+            Untagged::Struct(s) => {
+                self.in_variant(|| visit_struct(self.context, s, span, fields, visitor))
+            }
+            _ => Err(self.invalid_variant("struct variant")),
         }
     }
 }
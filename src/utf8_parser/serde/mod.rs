@@ -2,19 +2,38 @@ use std::{fs::File, io::Read, path::Path};
 
 use serde::de::DeserializeOwned;
 
-pub use self::de::from_str;
+pub use self::{
+    de::{from_str, from_str_with_options, from_str_with_warnings, RonDeserializer, TaggedMode},
+    options::{DuplicateKeys, Options},
+    raw::{RawRon, RawRonBuf},
+    stream::{ReaderStreamDeserializer, StreamDeserializer},
+};
 use crate::Error;
 
 mod de;
+mod options;
+mod raw;
+mod stream;
 #[cfg(test)]
 mod tests;
 
-pub fn from_reader<R: Read, T: DeserializeOwned>(mut reader: R) -> Result<T, Error> {
+pub fn from_reader<R: Read, T: DeserializeOwned>(reader: R) -> Result<T, Error> {
+    from_reader_with_options(reader, Options::default())
+}
+
+pub fn from_reader_with_options<R: Read, T: DeserializeOwned>(
+    mut reader: R,
+    options: Options,
+) -> Result<T, Error> {
     let mut buf = String::new();
 
     reader.read_to_string(&mut buf).map_err(Error::from)?;
 
-    from_str(&buf)
+    // The buffer isn't needed anymore, so the error can take it for rendering
+    match from_str_with_options(&buf, options) {
+        Ok(t) => Ok(t),
+        Err(e) => Err(e.context_file_content(buf)),
+    }
 }
 
 pub fn from_file<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T, Error> {
@@ -0,0 +1,151 @@
+use crate::{
+    ast,
+    ast::{Attribute, Extension},
+    utf8_parser::serde::TaggedMode,
+};
+
+/// What to do with structs defining a field more than once
+/// and maps containing a key more than once
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DuplicateKeys {
+    /// Report an error pointing at the duplicate
+    #[default]
+    Error,
+    /// Pass all entries to the visitor, which usually means the last one wins
+    Allow,
+}
+
+/// Configures the serde deserializer.
+///
+/// Extensions enabled in the file (`#![enable(..)]`) are applied on top of these options.
+///
+/// ```
+/// use ron_reboot::utf8_parser::serde::{from_str_with_options, Options};
+///
+/// let options = Options::default().implicit_some(true);
+/// let x: Option<Option<u32>> = from_str_with_options("3", options).unwrap();
+///
+/// assert_eq!(x, Some(Some(3)));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Options {
+    pub(crate) implicit_some: bool,
+    pub(crate) implicit_outer_struct: bool,
+    pub(crate) unquoted_map_keys: bool,
+    pub(crate) unwrap_newtypes: bool,
+    pub(crate) strict_struct_names: bool,
+    pub(crate) recursion_limit: Option<usize>,
+    pub(crate) duplicate_keys: DuplicateKeys,
+    pub(crate) float_int_coercion: bool,
+    pub(crate) tagged: TaggedMode,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            implicit_some: false,
+            implicit_outer_struct: false,
+            unquoted_map_keys: false,
+            unwrap_newtypes: false,
+            strict_struct_names: true,
+            recursion_limit: Some(Options::DEFAULT_RECURSION_LIMIT),
+            duplicate_keys: DuplicateKeys::Error,
+            float_int_coercion: false,
+            tagged: TaggedMode::Ignore,
+        }
+    }
+}
+
+impl Options {
+    pub const DEFAULT_RECURSION_LIMIT: usize = crate::utf8_parser::DEFAULT_RECURSION_LIMIT;
+
+    /// Accept `x` where `Option`s are expected, like the `implicit_some` extension
+    pub fn implicit_some(mut self, enabled: bool) -> Self {
+        self.implicit_some = enabled;
+
+        self
+    }
+
+    /// Accept documents consisting of struct fields without the surrounding `(` and `)`,
+    /// like the `implicit_outer_struct` extension
+    pub fn implicit_outer_struct(mut self, enabled: bool) -> Self {
+        self.implicit_outer_struct = enabled;
+
+        self
+    }
+
+    /// Accept bare identifiers as map keys (`{name: 1}`), like the `unquoted_map_keys` extension
+    pub fn unquoted_map_keys(mut self, enabled: bool) -> Self {
+        self.unquoted_map_keys = enabled;
+
+        self
+    }
+
+    /// Accept the inner value for newtype structs, like the `unwrap_newtypes` extension
+    pub fn unwrap_newtypes(mut self, enabled: bool) -> Self {
+        self.unwrap_newtypes = enabled;
+
+        self
+    }
+
+    /// Error if the name of a struct in the file doesn't match the Rust type (the default)
+    pub fn strict_struct_names(mut self, enabled: bool) -> Self {
+        self.strict_struct_names = enabled;
+
+        self
+    }
+
+    /// Maximum nesting depth of lists, maps, structs, tuples and options,
+    /// `None` to disable the limit.
+    ///
+    /// The limit applies to both parsing and deserialization.
+    ///
+    /// Defaults to [`Options::DEFAULT_RECURSION_LIMIT`].
+    pub fn recursion_limit(mut self, limit: Option<usize>) -> Self {
+        self.recursion_limit = limit;
+
+        self
+    }
+
+    pub fn duplicate_keys(mut self, policy: DuplicateKeys) -> Self {
+        self.duplicate_keys = policy;
+
+        self
+    }
+
+    /// Accept floats without fractional part (e.g. `3.0`) for integer types.
+    ///
+    /// Integers are always accepted for float types.
+    pub fn float_int_coercion(mut self, enabled: bool) -> Self {
+        self.float_int_coercion = enabled;
+
+        self
+    }
+
+    /// See [`TaggedMode`]
+    pub fn tagged(mut self, mode: TaggedMode) -> Self {
+        self.tagged = mode;
+
+        self
+    }
+
+    /// Enables the extensions listed in the attributes of `ron`
+    pub(crate) fn with_attrs(mut self, ron: &ast::Ron) -> Self {
+        for attribute in &ron.attributes {
+            match &attribute.value {
+                Attribute::Enable(list) => {
+                    for extension in &list.value {
+                        match extension.value {
+                            Extension::UnwrapNewtypes => self.unwrap_newtypes = true,
+                            Extension::ImplicitSome => self.implicit_some = true,
+                            Extension::ImplicitOuterStruct => self.implicit_outer_struct = true,
+                            Extension::UnquotedMapKeys => self.unquoted_map_keys = true,
+                        }
+                    }
+                }
+            }
+        }
+
+        self
+    }
+}
@@ -0,0 +1,221 @@
+use std::cell::OnceCell;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::{
+    ast::Spanned,
+    spanned::{self, SpannedVisitor},
+    utf8_parser::serde::{de::from_str_at, Options},
+    Error, Location,
+};
+
+pub(crate) const NAME: &str = "$ron_reboot::RawRon";
+
+const EXPECTING: &str = "raw RON, which this deserializer doesn't support";
+
+/// The unparsed source of a value, for parts of a document whose type is only known later,
+/// like `serde_json::value::RawValue`
+///
+/// Deserializing it later reports the locations in the original document,
+/// so errors can still be rendered with its source.
+/// Only deserializers with access to the source support this, e.g. [`crate::from_str_serde`].
+///
+/// ```
+/// use ron_reboot::{from_str_serde, utf8_parser::serde::RawRon, Location};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Plugin<'a> {
+///     r#type: String,
+///     #[serde(borrow)]
+///     config: RawRon<'a>,
+/// }
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Logger {
+///     level: u8,
+/// }
+///
+/// let source = "(\n    type: \"logger\",\n    config: (level: -1),\n)";
+/// let plugin: Plugin = from_str_serde(source).unwrap();
+/// assert_eq!(plugin.config.get(), "(level: -1)");
+///
+/// let e = plugin.config.deserialize_into::<Logger>().unwrap_err();
+/// assert_eq!(e.start(), Some(Location { line: 3, column: 21 }));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RawRon<'a> {
+    source: &'a str,
+    start: Location,
+    end: Location,
+}
+
+impl<'a> RawRon<'a> {
+    /// The source text of the value
+    pub fn get(&self) -> &'a str {
+        self.source
+    }
+
+    pub fn start(&self) -> Location {
+        self.start
+    }
+
+    /// Exclusive
+    pub fn end(&self) -> Location {
+        self.end
+    }
+
+    pub fn to_raw_ron_buf(&self) -> RawRonBuf {
+        RawRonBuf {
+            source: self.source.to_owned(),
+            start: self.start,
+            end: self.end,
+        }
+    }
+
+    pub fn deserialize_into<T>(&self) -> Result<T, Error>
+    where
+        T: Deserialize<'a>,
+    {
+        self.deserialize_into_with_options(Options::default())
+    }
+
+    /// Attributes like `#![enable(..)]` of the original document don't apply to raw values,
+    /// so they have to be passed in `options`.
+    pub fn deserialize_into_with_options<T>(&self, options: Options) -> Result<T, Error>
+    where
+        T: Deserialize<'a>,
+    {
+        from_str_at(self.source, self.start, options)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for RawRon<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Spanned { start, value, end } = deserializer.deserialize_struct(
+            NAME,
+            spanned::FIELDS,
+            SpannedVisitor::<&'de str>::new(EXPECTING),
+        )?;
+
+        Ok(RawRon {
+            source: value,
+            start,
+            end,
+        })
+    }
+}
+
+/// Owned version of [`RawRon`], e.g. for [`crate::utf8_parser::serde::from_reader`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RawRonBuf {
+    source: String,
+    start: Location,
+    end: Location,
+}
+
+impl RawRonBuf {
+    pub fn get(&self) -> &str {
+        &self.source
+    }
+
+    pub fn start(&self) -> Location {
+        self.start
+    }
+
+    /// Exclusive
+    pub fn end(&self) -> Location {
+        self.end
+    }
+
+    pub fn as_raw_ron(&self) -> RawRon<'_> {
+        RawRon {
+            source: &self.source,
+            start: self.start,
+            end: self.end,
+        }
+    }
+
+    pub fn deserialize_into<'a, T>(&'a self) -> Result<T, Error>
+    where
+        T: Deserialize<'a>,
+    {
+        self.as_raw_ron().deserialize_into()
+    }
+
+    pub fn deserialize_into_with_options<'a, T>(&'a self, options: Options) -> Result<T, Error>
+    where
+        T: Deserialize<'a>,
+    {
+        self.as_raw_ron().deserialize_into_with_options(options)
+    }
+}
+
+impl<'de> Deserialize<'de> for RawRonBuf {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let Spanned { start, value, end } = deserializer.deserialize_struct(
+            NAME,
+            spanned::FIELDS,
+            SpannedVisitor::<String>::new(EXPECTING),
+        )?;
+
+        Ok(RawRonBuf {
+            source: value,
+            start,
+            end,
+        })
+    }
+}
+
+/// The document being deserialized, to slice the source of `RawRon`s out of
+#[derive(Debug)]
+pub(crate) struct Source<'de> {
+    text: &'de str,
+    /// Location of the start of `text`
+    start: Location,
+    /// Byte offsets of the lines after the first one, found when the first `RawRon` is sliced
+    line_starts: OnceCell<Vec<usize>>,
+}
+
+impl<'de> Source<'de> {
+    pub(crate) fn new(text: &'de str, start: Location) -> Self {
+        Source {
+            text,
+            start,
+            line_starts: OnceCell::new(),
+        }
+    }
+
+    /// The text between `start` and `end`
+    pub(crate) fn slice(&self, start: Location, end: Location) -> &'de str {
+        &self.text[self.offset(start)..self.offset(end)]
+    }
+
+    fn offset(&self, location: Location) -> usize {
+        let line_starts = self.line_starts.get_or_init(|| {
+            self.text
+                .match_indices('\n')
+                .map(|(newline, _)| newline + 1)
+                .collect()
+        });
+
+        let (line_start, column) = match location.line.saturating_sub(self.start.line) {
+            0 => (0, self.start.column),
+            line => match line_starts.get(line as usize - 1) {
+                Some(&line_start) => (line_start, 1),
+                None => return self.text.len(),
+            },
+        };
+
+        self.text[line_start..]
+            .char_indices()
+            .nth(location.column.saturating_sub(column) as usize)
+            .map_or(self.text.len(), |(offset, _)| line_start + offset)
+    }
+}
@@ -0,0 +1,336 @@
+use std::{
+    io::{BufRead, BufReader, Read},
+    marker::PhantomData,
+};
+
+use serde::de::{Deserialize, DeserializeOwned};
+
+use crate::{
+    ast,
+    utf8_parser::{
+        ast_from_str_partial,
+        serde::{de::from_ron_at, Options},
+        whitespace_len,
+    },
+    Error, Location,
+};
+
+/// Deserializes consecutive top-level values, e.g. one value per line ("RON Lines")
+///
+/// Locations in errors point into the whole stream. After a syntax error,
+/// parsing continues at the next line which starts with a value in its first column.
+///
+/// ```
+/// use ron_reboot::{utf8_parser::serde::StreamDeserializer, Location};
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize, PartialEq)]
+/// enum Event {
+///     Login { user: String },
+///     Logout { user: String },
+/// }
+///
+/// let log = "Login(user: \"ferris\")\nLogout(user: \"ferris\"\nLogin(user: \"corro\")\n";
+/// let events: Vec<_> = StreamDeserializer::<Event>::new(log).collect();
+///
+/// assert_eq!(events.len(), 3);
+/// // `Logout(..)` is missing its `)`, so the next line is unexpected
+/// assert_eq!(events[1].as_ref().unwrap_err().start(), Some(Location { line: 3, column: 1 }));
+/// assert_eq!(
+///     events[2].as_ref().unwrap(),
+///     &Event::Login { user: "corro".to_owned() }
+/// );
+/// ```
+pub struct StreamDeserializer<'de, T> {
+    /// The documents not deserialized yet
+    source: &'de str,
+    /// Location of the start of `source`
+    location: Location,
+    options: Options,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T> StreamDeserializer<'de, T> {
+    pub fn new(source: &'de str) -> Self {
+        Self::with_options(source, Options::default())
+    }
+
+    /// Attributes like `#![enable(..)]` apply to the document they are in only
+    pub fn with_options(source: &'de str, options: Options) -> Self {
+        StreamDeserializer {
+            source,
+            location: Location { line: 1, column: 1 },
+            options,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> Iterator for StreamDeserializer<'de, T>
+where
+    T: Deserialize<'de>,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let source = self.source;
+        let (res, len) = match step(source, self.location, self.options) {
+            Step::End => return None,
+            Step::Document {
+                ron,
+                text,
+                start,
+                len,
+            } => (from_ron_at(&ron, text, start, self.options), len),
+            Step::Error { error, len, .. } => (Err(error), len),
+        };
+
+        self.source = &source[len..];
+        self.location = self.location.after(&source[..len]);
+
+        Some(res)
+    }
+}
+
+/// Like [`StreamDeserializer`], but reads the documents line by line from `R`,
+/// so values are available as soon as their last line has been read
+///
+/// An io error ends the stream.
+///
+/// A document which is still unfinished after [`max_lines`](Self::max_lines) lines
+/// is reported as syntax error, so an unclosed delimiter doesn't buffer the rest of the stream.
+pub struct ReaderStreamDeserializer<R, T> {
+    reader: BufReader<R>,
+    /// Lines read, but not deserialized yet
+    buffer: String,
+    /// Location of the start of `buffer`
+    location: Location,
+    eof: bool,
+    /// Lines are skipped until the next document after a syntax error
+    skipping: bool,
+    max_lines: Option<usize>,
+    options: Options,
+    marker: PhantomData<T>,
+}
+
+impl<R, T> ReaderStreamDeserializer<R, T>
+where
+    R: Read,
+{
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, Options::default())
+    }
+
+    pub fn with_options(reader: R, options: Options) -> Self {
+        ReaderStreamDeserializer {
+            reader: BufReader::new(reader),
+            buffer: String::new(),
+            location: Location { line: 1, column: 1 },
+            eof: false,
+            skipping: false,
+            max_lines: Some(Self::DEFAULT_MAX_LINES),
+            options,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R, T> ReaderStreamDeserializer<R, T> {
+    pub const DEFAULT_MAX_LINES: usize = 1000;
+
+    /// Maximum number of lines a document may span, `None` to disable the limit.
+    ///
+    /// Until a document is finished, it is parsed again for every line read.
+    ///
+    /// Defaults to [`ReaderStreamDeserializer::DEFAULT_MAX_LINES`].
+    pub fn max_lines(mut self, limit: Option<usize>) -> Self {
+        self.max_lines = limit;
+
+        self
+    }
+
+    /// Whether the buffer holds as many lines as a document may span
+    fn exceeds_max_lines(&self) -> bool {
+        match self.max_lines {
+            Some(limit) => self.buffer.matches('\n').count() >= limit,
+            None => false,
+        }
+    }
+}
+
+impl<R, T> ReaderStreamDeserializer<R, T>
+where
+    R: Read,
+{
+    /// Appends the next line to the buffer
+    fn read_line(&mut self) -> Result<(), Error> {
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => self.eof = true,
+            Ok(_) => {}
+            Err(e) => {
+                self.eof = true;
+                self.buffer.clear();
+
+                return Err(e.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R, T> Iterator for ReaderStreamDeserializer<R, T>
+where
+    R: Read,
+    T: DeserializeOwned,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Drop lines until the next document after a syntax error
+            if self.skipping && !starts_document(&self.buffer) {
+                if self.eof {
+                    return None;
+                }
+
+                self.location = self.location.after(&self.buffer);
+                self.buffer.clear();
+
+                if let Err(e) = self.read_line() {
+                    return Some(Err(e));
+                }
+
+                continue;
+            }
+            self.skipping = false;
+
+            let next = match step(&self.buffer, self.location, self.options) {
+                Step::End if self.eof => return None,
+                Step::End => None,
+                Step::Document {
+                    ron,
+                    text,
+                    start,
+                    len,
+                } => Some((from_ron_at(&ron, text, start, self.options), len)),
+                // The document may continue on the next line
+                Step::Error { unfinished, .. }
+                    if unfinished && !self.eof && !self.exceeds_max_lines() =>
+                {
+                    None
+                }
+                Step::Error {
+                    error,
+                    len,
+                    recovered,
+                    unfinished,
+                } => {
+                    self.skipping = !recovered;
+
+                    let error = match unfinished && !self.eof {
+                        true => error.context_note(format!(
+                            "the document is unfinished after {} lines",
+                            self.max_lines.unwrap_or_default()
+                        )),
+                        false => error,
+                    };
+
+                    Some((Err(error), len))
+                }
+            };
+
+            match next {
+                Some((res, len)) => {
+                    self.location = self.location.after(&self.buffer[..len]);
+                    self.buffer.drain(..len);
+
+                    return Some(res);
+                }
+                None => {
+                    if let Err(e) = self.read_line() {
+                        return Some(Err(e));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The next document of a stream
+enum Step<'s> {
+    /// Nothing but whitespace & comments is left
+    End,
+    Document {
+        ron: ast::Ron<'s>,
+        /// The source of the document
+        text: &'s str,
+        start: Location,
+        /// Length in bytes, including the whitespace around the document
+        len: usize,
+    },
+    /// The document couldn't be parsed, `len` bytes are skipped to continue after it
+    Error {
+        error: Error,
+        len: usize,
+        /// Whether the next document was found, otherwise all of `source` is skipped
+        recovered: bool,
+        /// Whether the parser reached the end of `source`, so more input may fix the error
+        unfinished: bool,
+    },
+}
+
+/// Parses the first document in `source`, which begins at `location`
+fn step(source: &str, location: Location, options: Options) -> Step<'_> {
+    let ws = whitespace_len(source);
+    let rest = &source[ws..];
+
+    if rest.is_empty() {
+        return Step::End;
+    }
+
+    let start = location.after(&source[..ws]);
+
+    match ast_from_str_partial(rest, start, options) {
+        Ok((ron, len)) => Step::Document {
+            ron,
+            text: &rest[..len],
+            start,
+            len: ws + len,
+        },
+        Err((error, unfinished)) => {
+            let recovery = recovery_point(rest);
+
+            Step::Error {
+                error,
+                len: ws + recovery.unwrap_or(rest.len()),
+                recovered: recovery.is_some(),
+                unfinished,
+            }
+        }
+    }
+}
+
+/// Whether `line` starts with a value in its first column,
+/// i.e. not with whitespace, a closing delimiter or a comment
+fn starts_document(line: &str) -> bool {
+    matches!(
+        line.chars().next(),
+        Some(c) if !c.is_whitespace() && !matches!(c, ')' | ']' | '}' | ',' | '/')
+    )
+}
+
+/// Offset of the first line after the first one which [`starts_document`]
+fn recovery_point(rest: &str) -> Option<usize> {
+    let mut offset = rest.find('\n')? + 1;
+
+    for line in rest[offset..].split_inclusive('\n') {
+        if starts_document(line) {
+            return Some(offset);
+        }
+
+        offset += line.len();
+    }
+
+    None
+}
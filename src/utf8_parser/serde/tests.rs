@@ -2,7 +2,17 @@ use std::{collections::HashMap, hash::Hash, iter::FromIterator};
 
 use serde::Deserialize;
 
-use crate::{error::ErrorKind::*, utf8_parser::serde::from_str as normal_from_str};
+use crate::{
+    error::ErrorKind::*,
+    utf8_parser::{
+        ast_from_str,
+        serde::{
+            from_str as normal_from_str, from_str_with_options, from_str_with_warnings,
+            DuplicateKeys, Options, RonDeserializer, TaggedMode,
+        },
+    },
+    Label, Location, Note, NoteKind,
+};
 
 // Custom wrapper for tests to make error easier to read
 pub fn from_str<'a, T>(s: &'a str) -> Result<T, crate::error::Error>
@@ -193,6 +203,475 @@ fn structs_nested() {
     );
 }
 
+#[test]
+fn duplicate_fields() {
+    let e = from_str::<MyStruct>(r#"(x: true, y: "a", x: false)"#).unwrap_err();
+
+    assert_eq!(e.kind, DuplicateField("x".to_owned()));
+    assert_eq!(
+        e.start(),
+        Some(Location {
+            line: 1,
+            column: 19
+        })
+    );
+    assert_eq!(
+        e.labels(),
+        &[Label {
+            start: Location { line: 1, column: 2 },
+            end: Location { line: 1, column: 3 },
+            message: "first defined here".to_owned(),
+            file_id: None,
+        }]
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct Strict {
+    int: i32,
+    float: f32,
+}
+
+#[test]
+fn unknown_field_suggestions() {
+    let e = from_str::<Strict>("(int: 1, flaot: 2.0)").unwrap_err();
+
+    assert_eq!(
+        e.start(),
+        Some(Location {
+            line: 1,
+            column: 10
+        })
+    );
+    assert_eq!(
+        e.notes(),
+        &[
+            Note {
+                kind: NoteKind::Help,
+                message: "did you mean `float`?".to_owned(),
+            },
+            Note {
+                kind: NoteKind::Note,
+                message: "valid fields are `int`, `float`".to_owned(),
+            },
+        ]
+    );
+
+    // Too different for a suggestion, but the valid fields are listed
+    let e = from_str::<Strict>("(rename_me: 1, float: 2.0)").unwrap_err();
+    assert_eq!(e.notes().len(), 1);
+    assert_eq!(e.notes()[0].message, "valid fields are `int`, `float`");
+}
+
+#[test]
+fn unknown_variant_suggestions() {
+    let e = from_str::<MyEnum>("UnitVarient1").unwrap_err();
+
+    assert_eq!(e.start(), Some(Location { line: 1, column: 1 }));
+    assert_eq!(e.notes()[0].message, "did you mean `UnitVariant1`?");
+    assert!(e.notes()[1]
+        .message
+        .starts_with("valid variants are `UnitVariant1`, "));
+}
+
+#[test]
+fn missing_field() {
+    let e = from_str::<MyStruct>("(\n    x: true,\n)").unwrap_err();
+
+    assert_eq!(e.kind, MissingField("y".to_owned()));
+    assert_eq!(e.start(), Some(Location { line: 3, column: 1 }));
+    assert_eq!(e.end(), Some(Location { line: 3, column: 2 }));
+    assert_eq!(
+        e.labels(),
+        &[Label {
+            start: Location { line: 1, column: 1 },
+            end: Location { line: 1, column: 2 },
+            message: "struct starts here".to_owned(),
+            file_id: None,
+        }]
+    );
+    assert_eq!(
+        e.notes(),
+        &[Note {
+            kind: NoteKind::Note,
+            message: "present fields are `x`".to_owned(),
+        }]
+    );
+
+    // The misspelled field is ignored
+    let e = from_str::<MyStruct>(r#"MyStruct(x: true, yy: "a")"#).unwrap_err();
+    assert_eq!(
+        e.start(),
+        Some(Location {
+            line: 1,
+            column: 26
+        })
+    );
+    assert_eq!(e.labels()[0].start, Location { line: 1, column: 9 });
+    assert_eq!(
+        e.notes()[1].message,
+        "`yy` is not a field, did you mean `y`?"
+    );
+
+    // Nested structs point at their own delimiter
+    let e = from_str::<MyNestedStruct>("(foo: (x: true))").unwrap_err();
+    assert_eq!(
+        e.start(),
+        Some(Location {
+            line: 1,
+            column: 15
+        })
+    );
+    assert_eq!(e.labels()[0].start, Location { line: 1, column: 7 });
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+enum Flag {
+    Unit,
+    Struct { optional: Option<u32> },
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Flags {
+    flags: Vec<Flag>,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Mapping {
+    mapping: HashMap<String, Flags>,
+}
+
+#[test]
+fn error_paths() {
+    let e = from_str::<Mapping>(
+        r#"(mapping: {"a": (flags: [Unit, Unit, Unit, Struct(optional: Some(-1))])})"#,
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        e.path().unwrap().to_string(),
+        r#"mapping["a"].flags[3].Struct.optional"#
+    );
+    assert_eq!(
+        e.to_string(),
+        format!(
+            "error at 1:66 in `mapping[\"a\"].flags[3].Struct.optional`: {}",
+            e.kind
+        )
+    );
+
+    let e = from_str::<HashMap<i32, (bool, bool)>>("{1: (true, 2)}").unwrap_err();
+    assert_eq!(e.path().unwrap().to_string(), "[1][1]");
+
+    // Errors at the top level have no path
+    assert_eq!(from_str::<bool>("1").unwrap_err().path(), None);
+}
+
+#[test]
+fn warnings_for_ignored_fields() {
+    let (point, warnings) =
+        from_str_with_warnings::<Point>("(x: 1, yy: [2, 3])", Options::default()).unwrap();
+
+    assert_eq!(point, Point { x: 1, y: None });
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].kind, IgnoredField("yy".to_owned()));
+    // The warning covers the whole entry
+    assert_eq!(warnings[0].start(), Some(Location { line: 1, column: 8 }));
+    assert_eq!(
+        warnings[0].end(),
+        Some(Location {
+            line: 1,
+            column: 18
+        })
+    );
+    assert_eq!(warnings[0].path().unwrap().to_string(), "yy");
+
+    let (_, warnings) = from_str_with_warnings::<HashMap<String, Vec<Point>>>(
+        r#"{"a": [(x: 1), (x: 2, z: 3)]}"#,
+        Options::default(),
+    )
+    .unwrap();
+
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].path().unwrap().to_string(), r#"["a"][1].z"#);
+
+    // Nothing is recorded without warnings
+    assert_eq!(
+        from_str::<Point>("(x: 1, yy: 2)").unwrap(),
+        Point { x: 1, y: None }
+    );
+}
+
+#[test]
+fn warnings_for_ignored_map_entries() {
+    let (point, warnings) =
+        from_str_with_warnings::<Point>(r#"{"x": 1, "z": 2}"#, Options::default()).unwrap();
+
+    assert_eq!(point, Point { x: 1, y: None });
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].kind, IgnoredValue);
+    assert_eq!(
+        warnings[0].start(),
+        Some(Location {
+            line: 1,
+            column: 10
+        })
+    );
+    assert_eq!(warnings[0].path().unwrap().to_string(), r#"["z"]"#);
+
+    let (_, warnings) =
+        from_str_with_warnings::<Point>("(x: 1, y: Some(2))", Options::default()).unwrap();
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn borrowed_ast() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Shape {
+        Circle { r: f64, label: std::string::String },
+    }
+
+    let ron = ast_from_str(r#"Circle(r: 1.5, label: "a\tb")"#).unwrap();
+    let original = ron.clone();
+
+    // Try the candidates one after another
+    assert!(MyEnum::deserialize(RonDeserializer::from_ron(&ron)).is_err());
+    assert_eq!(
+        Shape::deserialize(RonDeserializer::from_ron(&ron)).unwrap(),
+        Shape::Circle {
+            r: 1.5,
+            label: "a\tb".to_owned()
+        }
+    );
+
+    assert_eq!(ron, original);
+}
+
+#[test]
+fn variant_kind_mismatch() {
+    let e = from_str::<MyEnum>("StructVariant").unwrap_err();
+    assert_eq!(
+        e.kind,
+        Custom("invalid type: unit variant, expected struct variant".to_owned())
+    );
+
+    assert!(from_str::<MyEnum>("UnitVariant1(a: true)").is_err());
+    assert!(from_str::<MyEnum>("NewtypeVariant").is_err());
+    assert!(from_str::<MyEnum>("TupleVariant(a: 1)").is_err());
+}
+
+#[test]
+fn duplicate_map_keys() {
+    let e = from_str::<HashMap<String, i32>>(r#"{ "a": 1, "b": 2, "a": 3 }"#).unwrap_err();
+
+    assert_eq!(e.kind, DuplicateKey);
+    assert_eq!(
+        e.start(),
+        Some(Location {
+            line: 1,
+            column: 19
+        })
+    );
+    assert_eq!(e.labels()[0].start, Location { line: 1, column: 3 });
+
+    // Keys are compared by the value they deserialize to
+    let e = from_str::<HashMap<String, i32>>(r#"{ "a": 1, "\u{61}": 2 }"#).unwrap_err();
+    assert_eq!(e.kind, DuplicateKey);
+
+    let e = from_str::<HashMap<i64, i32>>("{ 1: 1, -1: 2, +1: 3 }").unwrap_err();
+    assert_eq!(e.kind, DuplicateKey);
+    assert_eq!(
+        e.start(),
+        Some(Location {
+            line: 1,
+            column: 16
+        })
+    );
+
+    let e = from_str::<HashMap<(u8, String), i32>>(r#"{ (1, "a"): 1, (+1, "\u{61}"): 2 }"#)
+        .unwrap_err();
+    assert_eq!(e.kind, DuplicateKey);
+}
+
+#[test]
+fn allow_duplicate_keys() {
+    assert_eq!(
+        from_str_with_options::<HashMap<String, i32>>(
+            r#"{ "a": 1, "a": 3 }"#,
+            Options::default().duplicate_keys(DuplicateKeys::Allow)
+        ),
+        Ok(HashMap::from_iter(vec![("a".to_owned(), 3)]))
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Shape {
+    Circle(f64),
+    Rect { w: u32, h: u32 },
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum ShapeOrList {
+    Shape(Shape),
+    List(Vec<Shape>),
+}
+
+#[test]
+fn tagged_as_map() {
+    let options = Options::default().tagged(TaggedMode::Map);
+    let de = |s| from_str_with_options::<Vec<ShapeOrList>>(s, options);
+
+    assert_eq!(
+        de("[Rect(w: 1, h: 2), [Circle(0.5)]]"),
+        Ok(vec![
+            ShapeOrList::Shape(Shape::Rect { w: 1, h: 2 }),
+            ShapeOrList::List(vec![Shape::Circle(0.5)]),
+        ])
+    );
+
+    assert_eq!(
+        from_str_with_options::<HashMap<String, HashMap<String, u32>>>("Rect(w: 1, h: 2)", options),
+        Ok(HashMap::from_iter(vec![(
+            "Rect".to_owned(),
+            HashMap::from_iter(vec![("w".to_owned(), 1), ("h".to_owned(), 2)])
+        )]))
+    );
+
+    let e = from_str_with_options::<HashMap<String, HashMap<String, u32>>>(
+        "Rect(w: 1, h: -2)",
+        options,
+    )
+    .unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "error at 1:15 in `Rect.h`: invalid value: integer `-2`, expected u32"
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Point {
+    x: i32,
+    y: Option<u8>,
+}
+
+#[test]
+fn options() {
+    let lenient = Options::default()
+        .implicit_some(true)
+        .strict_struct_names(false)
+        .float_int_coercion(true);
+
+    assert_eq!(
+        from_str_with_options("Vec2(x: -2.0, y: 3)", lenient),
+        Ok(Point { x: -2, y: Some(3) })
+    );
+    assert!(from_str_with_options::<Point>("(x: 1.5, y: 3)", lenient).is_err());
+    assert!(from_str_with_options::<Point>("(x: 256.0, y: None)", lenient).is_ok());
+    assert!(from_str_with_options::<Point>("(x: 0, y: 256.0)", lenient).is_err());
+
+    // Floats out of the range of the integer type are rejected, not saturated
+    let e = from_str_with_options::<u8>("-1.0", lenient).unwrap_err();
+    assert_eq!(
+        e.kind.to_string(),
+        "invalid value: floating point `-1.0`, expected u8"
+    );
+    let e = from_str_with_options::<i32>("1.0e20", lenient).unwrap_err();
+    assert_eq!(
+        e.kind.to_string(),
+        "invalid value: floating point `100000000000000000000.0`, expected i32"
+    );
+    assert!(from_str_with_options::<u64>("1.0e30", lenient).is_err());
+    assert!(from_str_with_options::<i64>("-1.0e19", lenient).is_err());
+    assert_eq!(
+        from_str_with_options::<u128>("1.0e20", lenient),
+        Ok(10u128.pow(20))
+    );
+    assert_eq!(
+        from_str_with_options::<i64>("-1.0e18", lenient),
+        Ok(-10i64.pow(18))
+    );
+
+    assert!(from_str::<Point>("Vec2(x: 1, y: None)").is_err());
+    assert!(from_str::<Point>("(x: 1.0, y: None)").is_err());
+}
+
+#[test]
+fn options_with_extensions() {
+    let options = Options::default().implicit_some(false);
+
+    assert_eq!(
+        from_str_with_options("#![enable(implicit_some)] (x: 1, y: 2)", options),
+        Ok(Point { x: 1, y: Some(2) })
+    );
+}
+
+#[test]
+fn recursion_limit() {
+    let options = Options::default().recursion_limit(Some(3));
+
+    assert_eq!(
+        from_str_with_options("[[[1]]]", options),
+        Ok(vec![vec![vec![1]]])
+    );
+
+    let e =
+        from_str_with_options::<Vec<Vec<Vec<Option<u8>>>>>("[[[Some(1)]]]", options).unwrap_err();
+    assert_eq!(e.kind, RecursionLimitExceeded(3));
+    assert_eq!(e.start(), Some(Location { line: 1, column: 9 }));
+
+    assert!(from_str_with_options::<Vec<Vec<Vec<Option<u8>>>>>(
+        "[[[Some(1)]]]",
+        options.recursion_limit(None)
+    )
+    .is_ok());
+}
+
+/// Records the variant name of any enum passed to `visit_enum`
+#[derive(Debug, PartialEq)]
+struct VariantName(String);
+
+impl<'de> Deserialize<'de> for VariantName {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        struct V;
+
+        impl<'de> serde::de::Visitor<'de> for V {
+            type Value = VariantName;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an enum")
+            }
+
+            fn visit_enum<A: serde::de::EnumAccess<'de>>(
+                self,
+                a: A,
+            ) -> Result<Self::Value, A::Error> {
+                use serde::de::VariantAccess;
+
+                let (name, variant) = a.variant::<String>()?;
+                variant.struct_variant(&[], serde::de::IgnoredAny)?;
+
+                Ok(VariantName(name))
+            }
+        }
+
+        d.deserialize_any(V)
+    }
+}
+
+#[test]
+fn tagged_as_enum() {
+    assert_eq!(
+        from_str_with_options("Foo(a: 1)", Options::default().tagged(TaggedMode::Enum)),
+        Ok(VariantName("Foo".to_owned()))
+    );
+    assert!(from_str::<VariantName>("Foo(a: 1)").is_err());
+}
+
 /*
 fn struct_fail() {
     let input = r#"Example(xyz: Asdf(
@@ -210,6 +689,35 @@ fn floats() {
     assert_eq!(from_str::<f64>("1.0e3"), Ok(1.0e3));
     assert_eq!(from_str::<f64>(".001e3"), Ok(0.001e3));
     assert_eq!(from_str::<f64>("+3.14"), Ok(3.14));
+
+    // Rounded correctly
+    assert_eq!(from_str::<f64>("0.30000000000000004"), Ok(0.1 + 0.2));
+    assert_eq!(from_str::<f64>("1.7976931348623157e308"), Ok(f64::MAX));
+    assert_eq!(from_str::<f64>("5.0e-324"), Ok(5.0e-324));
+    assert_eq!(from_str::<f64>("0.0"), Ok(0.0));
+    assert_eq!(
+        from_str::<f64>("-0.0e5").map(f64::to_bits),
+        Ok((-0.0f64).to_bits())
+    );
+
+    // 2^53 + 1 is halfway between two floats, rounded to the even one unless more follows,
+    // however many zeros come before
+    assert_eq!(
+        from_str::<f64>("9007199254740993.0"),
+        Ok(9007199254740992.0)
+    );
+    let zeros = "0".repeat(2000);
+    assert_eq!(
+        from_str::<f64>(&format!("9007199254740993.{}1", zeros)),
+        Ok(9007199254740994.0)
+    );
+    assert_eq!(from_str::<f64>(&format!("1.{}", zeros)), Ok(1.0));
+    assert_eq!(from_str::<f64>(&format!("0.{}1e2001", zeros)), Ok(1.0));
+
+    assert_eq!(from_str::<f64>("inf"), Ok(f64::INFINITY));
+    assert_eq!(from_str::<f64>("+inf"), Ok(f64::INFINITY));
+    assert_eq!(from_str::<f64>("-inf"), Ok(f64::NEG_INFINITY));
+    assert!(from_str::<f64>("NaN").unwrap().is_nan());
 }
 
 #[test]
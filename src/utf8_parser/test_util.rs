@@ -4,7 +4,7 @@ use crate::utf8_parser::InputParseErr;
 #[cfg(test)]
 macro_rules! eval {
     ($parser:expr,$input:expr) => {
-        $crate::utf8_parser::test_util::unwrap_pr1(eval!(@result $parser, $input))
+        $crate::utf8_parser::test_util::unwrap_pr1(eval!(@result $parser, $input), $input)
     };
     (@result $parser:expr,$input:expr) => {
         ($parser)($crate::utf8_parser::Input::new($input))
@@ -14,28 +14,47 @@ macro_rules! eval {
 #[cfg(test)]
 pub(crate) use eval;
 
-use crate::{print_error, utf8_parser::ok::IOk};
+use crate::{utf8_parser::ok::IOk, Error, Files};
 
 pub trait TestMockNew {
     fn new_mocked() -> Self;
 }
 
-pub fn unwrap_pr1<T>(r: Result<IOk<T>, InputParseErr>) -> T {
+pub fn unwrap_pr1<T>(r: Result<IOk<T>, InputParseErr>, input: &str) -> T {
     match r {
         Ok(ok) => ok.parsed,
         Err(InputParseErr::Recoverable(e) | InputParseErr::Fatal(e)) => {
-            print_error(&crate::Error::from(e)).unwrap();
+            print_error_with_source(Error::from(e), input);
             panic!();
         }
     }
 }
 
-pub fn unwrap_display<T>(r: Result<T, crate::error::Error>) -> T {
-    match r {
+/// Calls `parse` with `source`, printing the error with a snippet of `source` if it fails
+pub fn unwrap_display<'a, T>(
+    source: &'a str,
+    parse: impl FnOnce(&'a str) -> Result<T, Error>,
+) -> T {
+    match parse(source) {
         Ok(the_value) => the_value,
-        Err(r) => {
-            print_error(&r).unwrap();
+        Err(e) => {
+            print_error_with_source(e, source);
             panic!();
         }
     }
 }
+
+fn print_error_with_source(e: Error, source: &str) {
+    let mut files = Files::new();
+    let id = files.add("input.ron", source);
+
+    files.print_error(&e.context_file(id)).unwrap();
+}
+
+/// Converts the byte `offset` into `input` to a [`Location`](crate::Location) the same way
+/// the parser does.
+///
+/// `offset` has to be at a char boundary.
+pub fn location_at(input: &str, offset: usize) -> crate::Location {
+    crate::utf8_parser::Input::new(input).slice(offset..).into()
+}
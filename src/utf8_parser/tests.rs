@@ -19,6 +19,13 @@ fn trailing_commas() {
     );
 }
 
+#[test]
+fn trailing_commas_tuple() {
+    for input in ["(1, 2,)", "(1, 2, )", "Pos(\n    1,\n    2,\n)"] {
+        assert_eq!(eval!(@result expr, input).unwrap().remaining.len(), 0);
+    }
+}
+
 #[test]
 fn missing_colon() {
     let input = "Transform(pos 5)";
@@ -28,7 +35,10 @@ fn missing_colon() {
 #[test]
 fn exprs_struct() {
     let input = "Pos(x:-3,y:4)";
-    assert_eq!(Expr::Tagged(eval!(tagged, input)), eval!(expr, input));
+    assert_eq!(
+        Expr::Tagged(Box::new(eval!(tagged, input))),
+        eval!(expr, input)
+    );
 }
 
 #[test]
@@ -55,6 +65,36 @@ fn exprs_string() {
     );
 }
 
+#[test]
+fn exprs_keyword_prefixes() {
+    assert_eq!(eval!(expr, "true"), Expr::Bool(true));
+    assert_eq!(eval!(expr, "false"), Expr::Bool(false));
+
+    for input in ["t", "trueish", "f(1)", "r#true", "r#None", "r#Some(1)"] {
+        assert!(matches!(eval!(expr, input), Expr::Tagged(_)), "{}", input);
+    }
+    assert!(matches!(eval!(expr, "None"), Expr::Tagged(t) if t.is_optional()));
+}
+
+#[test]
+fn exprs_char() {
+    for (input, c) in [
+        ("'a'", 'a'),
+        ("'\"'", '"'),
+        ("'😂'", '😂'),
+        (r"'\''", '\''),
+        (r"'\\'", '\\'),
+        (r"'\n'", '\n'),
+        (r"'\u{1F980}'", '🦀'),
+    ] {
+        assert_eq!(eval!(expr, input), Expr::Char(c));
+    }
+
+    for input in ["''", "'ab'", "'''", r"'\'"] {
+        assert!(eval!(@result expr, input).is_err(), "{}", input);
+    }
+}
+
 #[test]
 fn strings() {
     assert_eq!(
@@ -134,9 +174,9 @@ fn maps() {
     let basic_map = Map::new_test(vec![
         (
             Expr::Str("my map key :)"),
-            Expr::Tagged(basic_struct.clone()),
+            Expr::Tagged(Box::new(basic_struct.clone())),
         ),
-        (Expr::Tagged(basic_struct), Expr::Bool(false)),
+        (Expr::Tagged(Box::new(basic_struct)), Expr::Bool(false)),
     ]);
 
     assert_eq!(
@@ -198,6 +238,24 @@ fn excl_mark() {
     assert_eq!(
         format!("{}", err),
         r#"could not match "expression" at 3:23 (`!`) because
-    expected one of an ascii letter or '_' at 3:23 (`!`)"#
+    expected a letter or '_' at 3:23 (`!`)"#
+    );
+}
+
+#[test]
+fn confusable_chars() {
+    let err = ast_from_str("(name: “Ferris”)").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "error at 1:8: parsing error: found '“' (U+201C LEFT DOUBLE QUOTATION MARK), \
+         which looks like '\"' but is a different character at 1:8"
+    );
+
+    let err = ast_from_str("[1， 2]").unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("(U+FF0C FULLWIDTH COMMA), which looks like ','"),
+        "{}",
+        err
     );
 }
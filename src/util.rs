@@ -24,3 +24,80 @@ pub fn write_pretty_list<T>(
     write!(f, " or ")?;
     write_t(f, i.next().unwrap())
 }
+
+/// Edit distance between `a` and `b` in chars, counting swapped neighbours as one edit
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    // distances[i][j] is the distance between the first i chars of a and the first j of b
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for i in 0..=a.len() {
+        for j in 0..=b.len() {
+            distances[i][j] = match (i, j) {
+                (0, j) => j,
+                (i, 0) => i,
+                (i, j) => {
+                    let substitution = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+                    let mut distance = substitution
+                        .min(distances[i - 1][j] + 1)
+                        .min(distances[i][j - 1] + 1);
+
+                    if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                        distance = distance.min(distances[i - 2][j - 2] + 1);
+                    }
+
+                    distance
+                }
+            };
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+/// The candidate most similar to `name`, if any is similar enough to be a typo
+///
+/// Names only differing in case, `_` and `-` (e.g. `MyVariant` vs `my-variant`) are always similar.
+pub fn similar_name<'c>(name: &str, candidates: &[&'c str]) -> Option<&'c str> {
+    let normalize = |s: &str| s.to_lowercase().replace(['-', '_'], "");
+    let normalized = normalize(name);
+
+    if let Some(same) = candidates.iter().find(|c| normalize(c) == normalized) {
+        return Some(same);
+    }
+
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates
+        .iter()
+        .map(|c| (edit_distance(name, c), *c))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, c)| c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{edit_distance, similar_name};
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("größe", "grösse"), 2);
+        assert_eq!(edit_distance("widht", "width"), 1);
+    }
+
+    #[test]
+    fn test_similar_name() {
+        let fields = ["width", "height", "my-field"];
+
+        assert_eq!(similar_name("widht", &fields), Some("width"));
+        assert_eq!(similar_name("hieght", &fields), Some("height"));
+        assert_eq!(similar_name("My_Field", &fields), Some("my-field"));
+        assert_eq!(similar_name("MyField", &fields), Some("my-field"));
+        assert_eq!(similar_name("depth", &fields), None);
+        assert_eq!(similar_name("x", &[]), None);
+    }
+}
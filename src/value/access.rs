@@ -0,0 +1,394 @@
+//! Accessors for reading & modifying values
+
+use std::ops;
+
+use crate::value::{Number, Value};
+
+/// Types which can index into a [`Value`]
+///
+/// * `usize` indexes lists & tuples
+/// * `str` / `String` index struct fields & maps with string keys
+pub trait ValueIndex {
+    #[doc(hidden)]
+    fn index_into<'v>(&self, v: &'v Value) -> Option<&'v Value>;
+    #[doc(hidden)]
+    fn index_into_mut<'v>(&self, v: &'v mut Value) -> Option<&'v mut Value>;
+    #[doc(hidden)]
+    fn remove_from(&self, v: &mut Value) -> Option<Value>;
+}
+
+impl ValueIndex for usize {
+    fn index_into<'v>(&self, v: &'v Value) -> Option<&'v Value> {
+        match v {
+            Value::List(elements) | Value::Tuple(_, elements) => elements.get(*self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'v>(&self, v: &'v mut Value) -> Option<&'v mut Value> {
+        match v {
+            Value::List(elements) | Value::Tuple(_, elements) => elements.get_mut(*self),
+            _ => None,
+        }
+    }
+
+    fn remove_from(&self, v: &mut Value) -> Option<Value> {
+        match v {
+            Value::List(elements) | Value::Tuple(_, elements) if *self < elements.len() => {
+                Some(elements.remove(*self))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn is_string_key(key: &Value, s: &str) -> bool {
+    matches!(key, Value::String(k) if k == s)
+}
+
+impl ValueIndex for str {
+    fn index_into<'v>(&self, v: &'v Value) -> Option<&'v Value> {
+        match v {
+            Value::Struct(_, fields) => fields.iter().find(|(k, _)| k == self).map(|(_, v)| v),
+            Value::Map(entries) => entries
+                .iter()
+                .find(|(k, _)| is_string_key(k, self))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'v>(&self, v: &'v mut Value) -> Option<&'v mut Value> {
+        match v {
+            Value::Struct(_, fields) => fields.iter_mut().find(|(k, _)| k == self).map(|(_, v)| v),
+            Value::Map(entries) => entries
+                .iter_mut()
+                .find(|(k, _)| is_string_key(k, self))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn remove_from(&self, v: &mut Value) -> Option<Value> {
+        match v {
+            Value::Struct(_, fields) => fields
+                .iter()
+                .position(|(k, _)| k == self)
+                .map(|i| fields.remove(i).1),
+            Value::Map(entries) => entries
+                .iter()
+                .position(|(k, _)| is_string_key(k, self))
+                .map(|i| entries.remove(i).1),
+            _ => None,
+        }
+    }
+}
+
+impl ValueIndex for String {
+    fn index_into<'v>(&self, v: &'v Value) -> Option<&'v Value> {
+        self.as_str().index_into(v)
+    }
+
+    fn index_into_mut<'v>(&self, v: &'v mut Value) -> Option<&'v mut Value> {
+        self.as_str().index_into_mut(v)
+    }
+
+    fn remove_from(&self, v: &mut Value) -> Option<Value> {
+        self.as_str().remove_from(v)
+    }
+}
+
+impl<T: ValueIndex + ?Sized> ValueIndex for &T {
+    fn index_into<'v>(&self, v: &'v Value) -> Option<&'v Value> {
+        (**self).index_into(v)
+    }
+
+    fn index_into_mut<'v>(&self, v: &'v mut Value) -> Option<&'v mut Value> {
+        (**self).index_into_mut(v)
+    }
+
+    fn remove_from(&self, v: &mut Value) -> Option<Value> {
+        (**self).remove_from(v)
+    }
+}
+
+impl Value {
+    /// Returns the list / tuple element or the struct field / map entry for `index`,
+    /// if there is one.
+    pub fn get<I: ValueIndex>(&self, index: I) -> Option<&Value> {
+        index.index_into(self)
+    }
+
+    /// Mutable version of [`Value::get`]
+    pub fn get_mut<I: ValueIndex>(&mut self, index: I) -> Option<&mut Value> {
+        index.index_into_mut(self)
+    }
+
+    /// Follows a `.` separated path of field names, map keys and list indices.
+    ///
+    /// ```
+    /// use ron_reboot::Value;
+    ///
+    /// let value: Value = r#"(players: [(name: "a"), (name: "b")])"#.parse().unwrap();
+    ///
+    /// assert_eq!(value.get_path("players.1.name"), Some(&Value::String("b".to_owned())));
+    /// assert_eq!(value.get_path("players.2.name"), None);
+    /// ```
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        path.split('.')
+            .try_fold(self, |value, segment| match segment.parse::<usize>() {
+                Ok(i) => value.get(i),
+                Err(_) => value.get(segment),
+            })
+    }
+
+    /// Mutable version of [`Value::get_path`]
+    pub fn get_path_mut(&mut self, path: &str) -> Option<&mut Value> {
+        path.split('.')
+            .try_fold(self, |value, segment| match segment.parse::<usize>() {
+                Ok(i) => value.get_mut(i),
+                Err(_) => value.get_mut(segment),
+            })
+    }
+
+    /// Sets the struct field or map entry `key` to `value`,
+    /// returning the previous value.
+    ///
+    /// New fields / entries are appended at the end.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is neither a struct nor a map.
+    pub fn insert(&mut self, key: impl Into<String>, value: Value) -> Option<Value> {
+        let key = key.into();
+
+        if let Some(existing) = self.get_mut(&key) {
+            return Some(std::mem::replace(existing, value));
+        }
+
+        match self {
+            Value::Struct(_, fields) => fields.push((key, value)),
+            Value::Map(entries) => entries.push((Value::String(key), value)),
+            _ => panic!("cannot insert field `{}` into {}", key, self.kind()),
+        }
+
+        None
+    }
+
+    /// Removes and returns the list / tuple element or the struct field / map entry for `index`
+    pub fn remove<I: ValueIndex>(&mut self, index: I) -> Option<Value> {
+        index.remove_from(self)
+    }
+
+    /// Returns the name of a named unit, tuple or struct
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Value::Unit(name) | Value::Tuple(name, _) | Value::Struct(name, _) => name.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_char(&self) -> Option<char> {
+        match self {
+            Value::Char(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if it is an integer
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Number(n) => n.as_i64(),
+            _ => None,
+        }
+    }
+
+    /// Returns the number as `f64`, converting integers
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(n.into_f64()),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<Number> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns `Some(inner)` for `Some(..)` and `None` for `None` (both wrapped in an `Option`)
+    pub fn as_option(&self) -> Option<Option<&Value>> {
+        match self {
+            Value::Option(o) => Some(o.as_deref()),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of a list or tuple
+    pub fn as_list(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::List(elements) | Value::Tuple(_, elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    /// Mutable version of [`Value::as_list`]
+    pub fn as_list_mut(&mut self) -> Option<&mut Vec<Value>> {
+        match self {
+            Value::List(elements) | Value::Tuple(_, elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&Vec<(Value, Value)>> {
+        match self {
+            Value::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    pub fn as_map_mut(&mut self) -> Option<&mut Vec<(Value, Value)>> {
+        match self {
+            Value::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Returns the fields of a struct
+    pub fn as_fields(&self) -> Option<&Vec<(String, Value)>> {
+        match self {
+            Value::Struct(_, fields) => Some(fields),
+            _ => None,
+        }
+    }
+
+    /// Mutable version of [`Value::as_fields`]
+    pub fn as_fields_mut(&mut self) -> Option<&mut Vec<(String, Value)>> {
+        match self {
+            Value::Struct(_, fields) => Some(fields),
+            _ => None,
+        }
+    }
+
+    /// Name of the variant, used in panic messages
+    fn kind(&self) -> &'static str {
+        match self {
+            Value::Bool(_) => "a bool",
+            Value::Char(_) => "a char",
+            Value::Map(_) => "a map",
+            Value::Number(_) => "a number",
+            Value::Option(_) => "an option",
+            Value::String(_) => "a string",
+            Value::List(_) => "a list",
+            Value::Unit(_) => "a unit",
+            Value::Tuple(_, _) => "a tuple",
+            Value::Struct(_, _) => "a struct",
+        }
+    }
+}
+
+/// # Panics
+///
+/// If the element / field does not exist, use [`Value::get`] to get an `Option` instead.
+impl<I: ValueIndex + std::fmt::Debug> ops::Index<I> for Value {
+    type Output = Value;
+
+    fn index(&self, index: I) -> &Value {
+        match index.index_into(self) {
+            Some(value) => value,
+            None => panic!("no element {:?} in {}", index, self.kind()),
+        }
+    }
+}
+
+/// # Panics
+///
+/// If the element / field does not exist, use [`Value::insert`] to add fields.
+impl<I: ValueIndex + std::fmt::Debug> ops::IndexMut<I> for Value {
+    fn index_mut(&mut self, index: I) -> &mut Value {
+        let kind = self.kind();
+
+        match index.index_into_mut(self) {
+            Some(value) => value,
+            None => panic!("no element {:?} in {}", index, kind),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "utf8_parser"))]
+mod tests {
+    use crate::Value;
+
+    fn value(s: &str) -> Value {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn index() {
+        let mut v = value(r#"Config(list: [1, (2, 3)], map: { "a": true, 1: false })"#);
+
+        assert_eq!(v["list"][1][0], value("2"));
+        assert_eq!(v["map"]["a"], Value::Bool(true));
+        assert_eq!(v.get("missing"), None);
+        assert_eq!(v.get_path("list.1.1"), Some(&value("3")));
+
+        v["map"]["a"] = Value::Bool(false);
+        *v.get_path_mut("list.0").unwrap() = value("-1");
+        assert_eq!(
+            v,
+            value(r#"Config(list: [-1, (2, 3)], map: { "a": false, 1: false })"#)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "no element 2 in a list")]
+    fn index_out_of_bounds() {
+        let _ = &value("[1, 2]")[2];
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut v = value("(a: 1, b: 2)");
+
+        assert_eq!(v.insert("a", value("3")), Some(value("1")));
+        assert_eq!(v.insert("c", value("4")), None);
+        assert_eq!(v.remove("b"), Some(value("2")));
+        assert_eq!(v.remove("b"), None);
+        assert_eq!(v, value("(a: 3, c: 4)"));
+
+        let mut v = value(r#"{ "a": [1, 2] }"#);
+        assert_eq!(v.insert("b", value("()")), None);
+        assert_eq!(v["a"].remove(0), Some(value("1")));
+        assert_eq!(v, value(r#"{ "a": [2], "b": () }"#));
+    }
+
+    #[test]
+    fn as_accessors() {
+        let v = value(r#"Point(x: 1, y: 2.5, name: "p", tag: Some(true))"#);
+
+        assert_eq!(v.name(), Some("Point"));
+        assert_eq!(v["x"].as_i64(), Some(1));
+        assert_eq!(v["x"].as_f64(), Some(1.0));
+        assert_eq!(v["y"].as_i64(), None);
+        assert_eq!(v["name"].as_str(), Some("p"));
+        assert_eq!(v["tag"].as_option(), Some(Some(&Value::Bool(true))));
+        assert_eq!(v.as_fields().map(Vec::len), Some(4));
+        assert_eq!(v.as_list(), None);
+    }
+}
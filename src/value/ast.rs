@@ -70,7 +70,10 @@ impl<'a> From<ast::Expr<'a>> for Value {
             Expr::Integer(i) => Value::Number(Number::Integer(i.into_i64())),
             Expr::Str(s) => Value::String(s.to_owned()),
             Expr::String(s) => Value::String(s),
+            Expr::Char(c) => Value::Char(c),
             Expr::Decimal(d) => Value::Number(Number::Float(Float::new(d.into()))),
+            Expr::NonFinite(n) => Value::Number(Number::Float(Float::new(n.into()))),
+            Expr::UnquotedKey(k) => Value::String(k.into_string()),
         }
     }
 }
@@ -0,0 +1,187 @@
+//! Implements Value -> RON string
+
+use std::fmt::{Display, Formatter, Result, Write};
+
+use crate::{
+    char_categories::{is_ident_first_char, is_ident_other_char},
+    value::{Number, Value},
+};
+
+/// Formats the value as RON.
+///
+/// The alternate flag (`{:#}`) enables pretty printing,
+/// with one element per line and trailing commas.
+///
+/// Parsing the output gives back the same value, except for empty structs and unnamed
+/// empty tuples: RON has no syntax for them, so they are printed as `Name()` or `()`
+/// and read back as a tuple or unit.
+///
+/// ```
+/// use ron_reboot::Value;
+///
+/// let value: Value = "Point(x: 1, y: -2.5)".parse().unwrap();
+///
+/// assert_eq!(value.to_string(), "Point(x: 1, y: -2.5)");
+/// assert_eq!(format!("{:#}", value), "Point(\n    x: 1,\n    y: -2.5,\n)");
+/// ```
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Printer {
+            pretty: f.alternate(),
+            indent: 0,
+            f,
+        }
+        .value(self)
+    }
+}
+
+struct Printer<'f, 'a> {
+    f: &'f mut Formatter<'a>,
+    pretty: bool,
+    indent: usize,
+}
+
+impl<'f, 'a> Printer<'f, 'a> {
+    fn value(&mut self, value: &Value) -> Result {
+        match value {
+            Value::Bool(b) => write!(self.f, "{}", b),
+            Value::Char(c) => {
+                self.f.write_char('\'')?;
+                escaped_char(self.f, *c, '\'')?;
+                self.f.write_char('\'')
+            }
+            Value::Map(m) => self.container('{', '}', m, |p, (k, v)| {
+                p.value(k)?;
+                p.f.write_str(": ")?;
+                p.value(v)
+            }),
+            Value::Number(n) => number(self.f, n),
+            Value::Option(None) => self.f.write_str("None"),
+            Value::Option(Some(v)) => {
+                self.f.write_str("Some(")?;
+                self.value(v)?;
+                self.f.write_char(')')
+            }
+            Value::String(s) => string(self.f, s),
+            Value::List(l) => self.container('[', ']', l, Printer::value),
+            Value::Unit(name) => match name {
+                Some(name) => ident(self.f, name),
+                None => self.f.write_str("()"),
+            },
+            Value::Tuple(name, elements) => {
+                if let Some(name) = name {
+                    ident(self.f, name)?;
+                }
+                self.container('(', ')', elements, Printer::value)
+            }
+            Value::Struct(name, fields) => {
+                if let Some(name) = name {
+                    ident(self.f, name)?;
+                }
+                self.container('(', ')', fields, |p, (k, v)| {
+                    ident(p.f, k)?;
+                    p.f.write_str(": ")?;
+                    p.value(v)
+                })
+            }
+        }
+    }
+
+    fn container<T>(
+        &mut self,
+        open: char,
+        close: char,
+        items: &[T],
+        mut item: impl FnMut(&mut Self, &T) -> Result,
+    ) -> Result {
+        self.f.write_char(open)?;
+
+        if items.is_empty() {
+            return self.f.write_char(close);
+        }
+
+        self.indent += 1;
+        for (i, x) in items.iter().enumerate() {
+            if self.pretty {
+                self.newline()?;
+            } else if i > 0 {
+                self.f.write_str(", ")?;
+            }
+
+            item(self, x)?;
+
+            if self.pretty {
+                self.f.write_char(',')?;
+            }
+        }
+        self.indent -= 1;
+
+        if self.pretty {
+            self.newline()?;
+        }
+
+        self.f.write_char(close)
+    }
+
+    fn newline(&mut self) -> Result {
+        self.f.write_char('\n')?;
+        for _ in 0..self.indent {
+            self.f.write_str("    ")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn number(f: &mut Formatter, n: &Number) -> Result {
+    match n {
+        Number::Integer(i) => write!(f, "{}", i),
+        Number::Float(float) => {
+            let float = float.get();
+            let s = format!("{:?}", float);
+
+            // RON decimals need a fractional part, `{:?}` omits it e.g. for `1e-7`
+            match s.find('e') {
+                Some(exp) if float.is_finite() && !s.contains('.') => {
+                    write!(f, "{}.0{}", &s[..exp], &s[exp..])
+                }
+                _ => f.write_str(&s),
+            }
+        }
+    }
+}
+
+fn string(f: &mut Formatter, s: &str) -> Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        escaped_char(f, c, '"')?;
+    }
+    f.write_char('"')
+}
+
+/// Writes `c` of a string or char literal delimited by `quote`
+fn escaped_char(f: &mut Formatter, c: char, quote: char) -> Result {
+    match c {
+        '\\' => f.write_str("\\\\"),
+        '\n' => f.write_str("\\n"),
+        '\r' => f.write_str("\\r"),
+        '\t' => f.write_str("\\t"),
+        c if c == quote => write!(f, "\\{}", c),
+        c if c.is_control() => write!(f, "\\u{{{:x}}}", c as u32),
+        c => f.write_char(c),
+    }
+}
+
+/// Writes `name` as identifier, using a raw identifier (`r#name`) if required
+fn ident(f: &mut Formatter, name: &str) -> Result {
+    let mut chars = name.chars();
+    let is_plain =
+        matches!(chars.next(), Some(c) if is_ident_first_char(c)) && chars.all(is_ident_other_char);
+    let is_keyword = matches!(name, "true" | "false" | "Some" | "None" | "inf" | "NaN");
+
+    if is_plain && !is_keyword {
+        f.write_str(name)
+    } else {
+        write!(f, "r#{}", name)
+    }
+}
@@ -0,0 +1,228 @@
+//! The `value!` macro
+
+/// Builds a [`Value`](crate::Value) from RON syntax.
+///
+/// Rust expressions can be interpolated with `#`, either as single token (`#x`)
+/// or in parentheses (`#(x + 1)`). They are converted with `Value::from`.
+///
+/// ```
+/// use ron_reboot::{value, Value};
+///
+/// let width = 800;
+/// let value = value!(Config(
+///     window: (width: #width, height: #(width * 3 / 4), title: Some("Game")),
+///     plugins: [Audio, Network(port: 8080)],
+///     keys: { "jump": Space, #("sh".to_owned() + "oot"): -1 },
+/// ));
+///
+/// assert_eq!(value["window"]["height"], Value::from(600));
+/// assert_eq!(
+///     value,
+///     r#"Config(
+///         window: (width: 800, height: 600, title: Some("Game")),
+///         plugins: [Audio, Network(port: 8080)],
+///         keys: { "jump": Space, "shoot": -1 },
+///     )"#
+///     .parse()
+///     .unwrap()
+/// );
+/// ```
+#[macro_export]
+macro_rules! value {
+    // === Comma separated elements (lists & tuples) ===
+    (@seq [$($done:expr,)*] []) => {
+        vec![$($done,)*]
+    };
+    (@seq [$($done:expr,)*] [$($cur:tt)+]) => {
+        vec![$($done,)* $crate::value!($($cur)+)]
+    };
+    (@seq [$($done:expr,)*] [$($cur:tt)+] , $($rest:tt)*) => {
+        $crate::value!(@seq [$($done,)* $crate::value!($($cur)+),] [] $($rest)*)
+    };
+    (@seq $done:tt [$($cur:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::value!(@seq $done [$($cur)* $next] $($rest)*)
+    };
+
+    // === Struct fields ===
+    (@fields [$($done:expr,)*]) => {
+        vec![$($done,)*]
+    };
+    (@fields $done:tt $key:ident : $($rest:tt)*) => {
+        $crate::value!(@field $done $key [] $($rest)*)
+    };
+    (@field [$($done:expr,)*] $key:ident [$($cur:tt)+]) => {
+        vec![$($done,)* $crate::value!(@field_entry $key [$($cur)+])]
+    };
+    (@field [$($done:expr,)*] $key:ident [$($cur:tt)+] , $($rest:tt)*) => {
+        $crate::value!(@fields [$($done,)* $crate::value!(@field_entry $key [$($cur)+]),] $($rest)*)
+    };
+    (@field $done:tt $key:ident [$($cur:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::value!(@field $done $key [$($cur)* $next] $($rest)*)
+    };
+    (@field_entry $key:ident [$($value:tt)+]) => {
+        (
+            stringify!($key).trim_start_matches("r#").to_owned(),
+            $crate::value!($($value)+),
+        )
+    };
+
+    // === Map entries ===
+    (@map [$($done:expr,)*] []) => {
+        vec![$($done,)*]
+    };
+    (@map $done:tt [$($key:tt)+] : $($rest:tt)*) => {
+        $crate::value!(@map_value $done [$($key)+] [] $($rest)*)
+    };
+    (@map $done:tt [$($key:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::value!(@map $done [$($key)* $next] $($rest)*)
+    };
+    (@map_value [$($done:expr,)*] [$($key:tt)+] [$($cur:tt)+]) => {
+        vec![$($done,)* ($crate::value!($($key)+), $crate::value!($($cur)+))]
+    };
+    (@map_value [$($done:expr,)*] [$($key:tt)+] [$($cur:tt)+] , $($rest:tt)*) => {
+        $crate::value!(
+            @map [$($done,)* ($crate::value!($($key)+), $crate::value!($($cur)+)),] [] $($rest)*
+        )
+    };
+    (@map_value $done:tt $key:tt [$($cur:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::value!(@map_value $done $key [$($cur)* $next] $($rest)*)
+    };
+
+    // === Untagged & tagged structs / tuples ===
+    (@untagged $name:expr, ()) => {
+        $crate::Value::Tuple($name, vec![])
+    };
+    (@untagged $name:expr, ($key:ident : $($rest:tt)*)) => {
+        $crate::Value::Struct($name, $crate::value!(@fields [] $key : $($rest)*))
+    };
+    (@untagged $name:expr, ($($rest:tt)*)) => {
+        $crate::Value::Tuple($name, $crate::value!(@seq [] [] $($rest)*))
+    };
+
+    // === Values ===
+    (# $e:tt) => {
+        $crate::Value::from($e)
+    };
+    (true) => {
+        $crate::Value::Bool(true)
+    };
+    (false) => {
+        $crate::Value::Bool(false)
+    };
+    (None) => {
+        $crate::Value::Option(None)
+    };
+    (Some($($inner:tt)+)) => {
+        $crate::Value::Option(Some(Box::new($crate::value!($($inner)+))))
+    };
+    ([$($elements:tt)*]) => {
+        $crate::Value::List($crate::value!(@seq [] [] $($elements)*))
+    };
+    ({$($entries:tt)*}) => {
+        $crate::Value::Map($crate::value!(@map [] [] $($entries)*))
+    };
+    (()) => {
+        $crate::Value::Unit(None)
+    };
+    (($($untagged:tt)+)) => {
+        $crate::value!(@untagged None, ($($untagged)+))
+    };
+    ($name:ident) => {
+        $crate::Value::Unit(Some(stringify!($name).trim_start_matches("r#").to_owned()))
+    };
+    ($name:ident $untagged:tt) => {
+        $crate::value!(
+            @untagged Some(stringify!($name).trim_start_matches("r#").to_owned()),
+            $untagged
+        )
+    };
+    ($lit:literal) => {
+        $crate::Value::from($lit)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{value::Number, Value};
+
+    #[test]
+    fn primitives() {
+        assert_eq!(value!(true), Value::Bool(true));
+        assert_eq!(value!(-3), Value::Number(Number::Integer(-3)));
+        assert_eq!(value!(2.5), Value::Number(Number::new(2.5)));
+        assert_eq!(value!("a"), Value::String("a".to_owned()));
+        assert_eq!(value!('c'), Value::Char('c'));
+        assert_eq!(value!(()), Value::Unit(None));
+        assert_eq!(
+            value!(Some(None)),
+            Value::Option(Some(Box::new(Value::Option(None))))
+        );
+    }
+
+    #[test]
+    fn containers() {
+        assert_eq!(
+            value!([1, (2, Foo), (a: [], r#type: {})]),
+            Value::List(vec![
+                Value::from(1),
+                Value::Tuple(
+                    None,
+                    vec![Value::from(2), Value::Unit(Some("Foo".to_owned()))]
+                ),
+                Value::Struct(
+                    None,
+                    vec![
+                        ("a".to_owned(), Value::List(vec![])),
+                        ("type".to_owned(), Value::Map(vec![])),
+                    ]
+                ),
+            ])
+        );
+        assert_eq!(
+            value!(Foo(a: 1,)),
+            Value::Struct(
+                Some("Foo".to_owned()),
+                vec![("a".to_owned(), Value::from(1))]
+            )
+        );
+        assert_eq!(value!(Foo()), Value::Tuple(Some("Foo".to_owned()), vec![]));
+        assert_eq!(
+            value!({ -1: "a", (1, 2): Some(Bar) }),
+            Value::Map(vec![
+                (Value::from(-1), Value::from("a")),
+                (
+                    Value::Tuple(None, vec![Value::from(1), Value::from(2)]),
+                    Value::Option(Some(Box::new(Value::Unit(Some("Bar".to_owned())))))
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn interpolation() {
+        let name = "x";
+        let list = vec![1u8, 2];
+
+        assert_eq!(
+            value!((a: #name, b: #(list.clone()), c: #(list.len()), d: [#(Some(1.5)), #(None::<bool>)])),
+            Value::Struct(
+                None,
+                vec![
+                    ("a".to_owned(), Value::from("x")),
+                    (
+                        "b".to_owned(),
+                        Value::List(vec![Value::from(1), Value::from(2)])
+                    ),
+                    ("c".to_owned(), Value::from(2u64)),
+                    (
+                        "d".to_owned(),
+                        Value::List(vec![
+                            Value::Option(Some(Box::new(Value::from(1.5)))),
+                            Value::Option(None),
+                        ])
+                    ),
+                ]
+            )
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Deep merging of values, e.g. to apply overrides on top of defaults
+
+use crate::value::Value;
+
+/// How [`Value::merge`] combines two lists
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ListMerge {
+    /// The list from the override replaces the original one
+    #[default]
+    Replace,
+    /// Elements from the override are appended to the original list
+    Append,
+    /// Elements with the same index are merged, additional elements are appended
+    ByIndex,
+}
+
+impl Value {
+    /// Merges `other` into `self`.
+    ///
+    /// * struct fields and map entries are merged recursively, new ones are appended
+    /// * `Some(a)` and `Some(b)` are merged to `Some(merge(a, b))`
+    /// * lists (and tuples) are combined according to `lists`
+    /// * everything else is replaced by `other`
+    ///
+    /// Structs (and tuples) are only merged if they have the same name or at least one of
+    /// them is unnamed, in which case the name is kept. Differently named ones are
+    /// replaced, as they are different types.
+    ///
+    /// ```
+    /// use ron_reboot::{value::ListMerge, Value};
+    ///
+    /// let mut config: Value = "(window: (width: 800, height: 600), plugins: [\"a\"])"
+    ///     .parse()
+    ///     .unwrap();
+    /// let overrides: Value = "(window: (width: 1024), plugins: [\"b\"])".parse().unwrap();
+    ///
+    /// config.merge(overrides, ListMerge::Append);
+    ///
+    /// assert_eq!(
+    ///     config,
+    ///     "(window: (width: 1024, height: 600), plugins: [\"a\", \"b\"])"
+    ///         .parse()
+    ///         .unwrap()
+    /// );
+    /// ```
+    pub fn merge(&mut self, other: Value, lists: ListMerge) {
+        match (self, other) {
+            (Value::Struct(name, fields), Value::Struct(other_name, other_fields))
+                if same_type(name, &other_name) =>
+            {
+                if other_name.is_some() {
+                    *name = other_name;
+                }
+
+                for (key, value) in other_fields {
+                    match fields.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, existing)) => existing.merge(value, lists),
+                        None => fields.push((key, value)),
+                    }
+                }
+            }
+            (Value::Map(entries), Value::Map(other_entries)) => {
+                for (key, value) in other_entries {
+                    match entries.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, existing)) => existing.merge(value, lists),
+                        None => entries.push((key, value)),
+                    }
+                }
+            }
+            (Value::Option(Some(inner)), Value::Option(Some(other_inner))) => {
+                inner.merge(*other_inner, lists)
+            }
+            (Value::List(elements), Value::List(other_elements)) if lists != ListMerge::Replace => {
+                merge_lists(elements, other_elements, lists)
+            }
+            (Value::Tuple(name, elements), Value::Tuple(other_name, other_elements))
+                if lists != ListMerge::Replace && same_type(name, &other_name) =>
+            {
+                if other_name.is_some() {
+                    *name = other_name;
+                }
+
+                merge_lists(elements, other_elements, lists)
+            }
+            (this, other) => *this = other,
+        }
+    }
+}
+
+fn same_type(name: &Option<String>, other: &Option<String>) -> bool {
+    match (name, other) {
+        (Some(name), Some(other)) => name == other,
+        _ => true,
+    }
+}
+
+fn merge_lists(elements: &mut Vec<Value>, other: Vec<Value>, lists: ListMerge) {
+    match lists {
+        ListMerge::Replace => *elements = other,
+        ListMerge::Append => elements.extend(other),
+        ListMerge::ByIndex => {
+            let mut other = other.into_iter();
+            for (element, other_element) in elements.iter_mut().zip(&mut other) {
+                element.merge(other_element, lists);
+            }
+            elements.extend(other);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "utf8_parser"))]
+mod tests {
+    use super::ListMerge;
+    use crate::Value;
+
+    fn merged(a: &str, b: &str, lists: ListMerge) -> Value {
+        let mut a: Value = a.parse().unwrap();
+        a.merge(b.parse().unwrap(), lists);
+
+        a
+    }
+
+    fn value(s: &str) -> Value {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn structs_and_maps() {
+        assert_eq!(
+            merged(
+                r#"(a: 1, b: (c: 2, d: 3), e: { "f": Some((g: 4)) })"#,
+                r#"Name(b: (d: 5), e: { "f": Some((h: 6)), "i": 7 }, j: None)"#,
+                ListMerge::Replace
+            ),
+            value(
+                r#"Name(a: 1, b: (c: 2, d: 5), e: { "f": Some((g: 4, h: 6)), "i": 7 }, j: None)"#
+            )
+        );
+    }
+
+    #[test]
+    fn named_structs() {
+        assert_eq!(
+            merged("A(a: 1, b: 2)", "A(b: 3)", ListMerge::Replace),
+            value("A(a: 1, b: 3)")
+        );
+        assert_eq!(
+            merged("A(a: 1, b: 2)", "(b: 3)", ListMerge::Replace),
+            value("A(a: 1, b: 3)")
+        );
+        assert_eq!(
+            merged("A(a: 1, b: 2)", "B(b: 3)", ListMerge::Replace),
+            value("B(b: 3)")
+        );
+        assert_eq!(merged("A(1, 2)", "B(3)", ListMerge::ByIndex), value("B(3)"));
+        assert_eq!(
+            merged("A(1, 2)", "(3,)", ListMerge::ByIndex),
+            value("A(3, 2)")
+        );
+    }
+
+    #[test]
+    fn replaces_different_kinds() {
+        assert_eq!(
+            merged("(a: (b: 1))", "(a: [1])", ListMerge::Append),
+            value("(a: [1])")
+        );
+        assert_eq!(
+            merged("(a: Some(1))", "(a: None)", ListMerge::Replace),
+            value("(a: None)")
+        );
+    }
+
+    #[test]
+    fn lists() {
+        let a = "[(a: 1), (a: 2)]";
+        let b = "[(b: 3)]";
+
+        assert_eq!(merged(a, b, ListMerge::Replace), value("[(b: 3)]"));
+        assert_eq!(
+            merged(a, b, ListMerge::Append),
+            value("[(a: 1), (a: 2), (b: 3)]")
+        );
+        assert_eq!(
+            merged(a, b, ListMerge::ByIndex),
+            value("[(a: 1, b: 3), (a: 2)]")
+        );
+        assert_eq!(merged("[1]", "[2, 3]", ListMerge::ByIndex), value("[2, 3]"));
+    }
+}
@@ -7,9 +7,18 @@ use std::{
     hash::{Hash, Hasher},
 };
 
+pub use self::{access::ValueIndex, merge::ListMerge, spanned::SpannedValue};
+
+mod access;
 mod ast;
+mod display;
+mod macros;
+mod merge;
 #[cfg(feature = "value_serde1")]
 mod ser_de;
+mod spanned;
+#[cfg(feature = "value_serde1")]
+mod spanned_de;
 
 /// A wrapper for a number, which can be either `f64` or `i64`.
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]
@@ -222,6 +231,72 @@ pub enum Value {
     Struct(Option<String>, Vec<(String, Value)>),
 }
 
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<char> for Value {
+    fn from(c: char) -> Self {
+        Value::Char(c)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_owned())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($ty:ty => $via:ty),*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(n: $ty) -> Self {
+                    Value::Number(Number::from(<$via>::from(n)))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number! {
+    i8 => i64, i16 => i64, i32 => i64, i64 => i64,
+    u8 => i64, u16 => i64, u32 => i64, u64 => u64,
+    f32 => f64, f64 => f64
+}
+
+impl From<usize> for Value {
+    fn from(n: usize) -> Self {
+        Value::Number(Number::from(n as u64))
+    }
+}
+
+impl From<Number> for Value {
+    fn from(n: Number) -> Self {
+        Value::Number(n)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(o: Option<T>) -> Self {
+        Value::Option(o.map(|v| Box::new(v.into())))
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(v: Vec<T>) -> Self {
+        Value::List(v.into_iter().map(Into::into).collect())
+    }
+}
+
 #[cfg(all(test, feature = "value_serde1", feature = "utf8_parser_serde1"))]
 mod tests {
     use std::{collections::BTreeMap, fmt::Debug};
@@ -276,7 +351,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore]
     fn char() {
         assert_same::<char>("'4'");
         assert_same::<char>("'c'");
@@ -308,6 +382,199 @@ mod tests {
         assert_same::<()>("()");
     }
 
+    fn spanned(s: &str) -> crate::ast::Spanned<SpannedValue> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn spanned_same_as_value() {
+        let s = "[Some(Point(x: 1.5, y: -2)), None]";
+
+        assert_eq!(Value::from(spanned(s)), s.parse::<Value>().unwrap());
+    }
+
+    #[test]
+    fn spanned_into_rust() {
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        enum Shape {
+            Circle { r: f64 },
+            Square(f64),
+        }
+
+        assert_eq!(
+            spanned("[Circle(r: 1.0), Square(2.0)]").into_rust_serde(),
+            Ok(vec![Shape::Circle { r: 1.0 }, Shape::Square(2.0)])
+        );
+    }
+
+    #[test]
+    fn spanned_fields() {
+        use crate::Location;
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Point {
+            x: crate::Spanned<i32>,
+        }
+
+        let point: Point = spanned("(x: 12)").into_rust_serde().unwrap();
+        assert_eq!(point.x.value, 12);
+        assert_eq!(point.x.start, Location { line: 1, column: 5 });
+        assert_eq!(point.x.end, Location { line: 1, column: 7 });
+    }
+
+    #[test]
+    fn spanned_error_locations() {
+        use crate::Location;
+
+        #[derive(Debug, serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        #[allow(dead_code)]
+        struct Plugin {
+            name: String,
+            priority: u8,
+        }
+
+        let e = spanned(
+            "[
+    (name: \"a\", priority: 1),
+    (name: \"b\", priority: 300),
+]",
+        )
+        .into_rust_serde::<Vec<Plugin>>()
+        .unwrap_err();
+        assert_eq!(
+            e.start(),
+            Some(Location {
+                line: 3,
+                column: 27
+            })
+        );
+        assert_eq!(
+            e.end(),
+            Some(Location {
+                line: 3,
+                column: 30
+            })
+        );
+
+        let e = spanned("(name: \"a\", prio: 1)")
+            .into_rust_serde::<Plugin>()
+            .unwrap_err();
+        assert_eq!(
+            e.start(),
+            Some(Location {
+                line: 1,
+                column: 13
+            })
+        );
+
+        let e = spanned("Plugn(name: \"a\", priority: 1)")
+            .into_rust_serde::<Plugin>()
+            .unwrap_err();
+        assert_eq!(e.start(), Some(Location { line: 1, column: 1 }));
+        assert_eq!(e.end(), Some(Location { line: 1, column: 6 }));
+    }
+
+    #[test]
+    fn error_paths() {
+        use crate::PathSegment;
+
+        #[derive(Debug, serde::Deserialize)]
+        #[allow(dead_code)]
+        enum Flag {
+            Unit,
+            Struct { optional: Option<u32> },
+        }
+
+        let value: Value = "{\"a\": [Unit, Struct(optional: Some(-1))]}"
+            .parse()
+            .unwrap();
+        let e = value
+            .into_rust_serde::<std::collections::HashMap<String, Vec<Flag>>>()
+            .unwrap_err();
+
+        assert_eq!(e.path().unwrap().to_string(), "[\"a\"][1].Struct.optional");
+        assert_eq!(
+            e.path().unwrap().segments()[..2],
+            [PathSegment::Key("\"a\"".to_owned()), PathSegment::Index(1)]
+        );
+        assert!(e
+            .to_string()
+            .starts_with("error in `[\"a\"][1].Struct.optional`: "));
+    }
+
+    #[test]
+    fn spanned_error_paths() {
+        use crate::Location;
+
+        #[derive(Debug, serde::Deserialize)]
+        #[allow(dead_code)]
+        enum Flag {
+            Unit,
+            Struct { optional: Option<u32> },
+        }
+
+        let e = spanned("{\"a\": [Unit, Struct(optional: Some(-1))]}")
+            .into_rust_serde::<std::collections::HashMap<String, Vec<Flag>>>()
+            .unwrap_err();
+
+        assert_eq!(e.path().unwrap().to_string(), "[\"a\"][1].Struct.optional");
+        assert_eq!(
+            e.start(),
+            Some(Location {
+                line: 1,
+                column: 36
+            })
+        );
+    }
+
+    #[test]
+    fn display() {
+        let value: Value = "Config(a: Some([1, 2]), b: {\"c\": ()}, d: [], e: r#f.g)"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            value.to_string(),
+            "Config(a: Some([1, 2]), b: {\"c\": ()}, d: [], e: r#f.g)"
+        );
+        assert_eq!(
+            format!("{:#}", value),
+            "Config(
+    a: Some([
+        1,
+        2,
+    ]),
+    b: {
+        \"c\": (),
+    },
+    d: [],
+    e: r#f.g,
+)"
+        );
+
+        let value: Value = "Größe(länge: 1)".parse().unwrap();
+        assert_eq!(value.to_string(), "Größe(länge: 1)");
+    }
+
+    #[test]
+    fn serialize() {
+        let value = Value::List(vec![
+            "Some(Point(x: 1, y: 2.0))".parse().unwrap(),
+            Value::Option(None),
+            Value::Tuple(
+                Some("Pair".to_owned()),
+                vec![Value::String("a".to_owned()), Value::Char('b')],
+            ),
+        ]);
+
+        // names are kept as the key of a map, like externally tagged enums
+        assert_eq!(
+            ron::ser::to_string(&value).unwrap(),
+            "[Some({\"Point\":{\"x\":1,\"y\":2}}),None,{\"Pair\":(\"a\",'b')}]"
+        );
+    }
+
     fn eval_serde_val(s: &str) -> Value {
         crate::utf8_parser::serde::from_str(s).unwrap()
     }
@@ -329,18 +596,26 @@ mod tests {
     }
 
     #[test]
-    #[ignore]
     fn test_floats() {
         assert_eq!(
             eval_serde_val("(inf, -inf, NaN)"),
-            Value::Tuple(
+            // serde's data model has no tuples of unknown length
+            Value::List(vec![
+                Value::Number(Number::new(std::f64::INFINITY)),
+                Value::Number(Number::new(std::f64::NEG_INFINITY)),
+                Value::Number(Number::new(std::f64::NAN)),
+            ]),
+        );
+        assert_eq!(
+            "(inf, -inf, NaN)".parse::<Value>(),
+            Ok(Value::Tuple(
                 None,
                 vec![
-                    Value::Number(Number::new(std::f64::INFINITY)),
-                    Value::Number(Number::new(std::f64::NEG_INFINITY)),
-                    Value::Number(Number::new(std::f64::NAN)),
+                    Value::Number(Number::new(f64::INFINITY)),
+                    Value::Number(Number::new(f64::NEG_INFINITY)),
+                    Value::Number(Number::new(f64::NAN)),
                 ]
-            ),
+            )),
         );
     }
 
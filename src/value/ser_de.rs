@@ -2,14 +2,16 @@ use std::fmt;
 
 use serde::{
     de::{
-        DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, Error as SerdeError,
-        MapAccess, SeqAccess, VariantAccess, Visitor,
+        value::StrDeserializer, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess,
+        Error as SerdeError, MapAccess, SeqAccess, VariantAccess, Visitor,
     },
-    forward_to_deserialize_any, Deserialize,
+    forward_to_deserialize_any,
+    ser::{SerializeMap, SerializeSeq, SerializeTuple},
+    Deserialize, Serialize, Serializer,
 };
 
 use super::Value;
-use crate::{value::Number, Error};
+use crate::{value::Number, Error, PathSegment};
 
 impl Value {
     /// Tries to deserialize this `Value` into `T`.
@@ -21,6 +23,91 @@ impl Value {
     }
 }
 
+/// Serializer implementation for RON `Value`.
+///
+/// serde requires the names of structs to be `&'static str`, so named units, tuples and structs
+/// are serialized as a map from their name to the unnamed value, like externally tagged enums.
+/// Unnamed structs are serialized as maps.
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Value::Bool(b) => serializer.serialize_bool(*b),
+            Value::Char(c) => serializer.serialize_char(*c),
+            Value::Map(m) => {
+                let mut map = serializer.serialize_map(Some(m.len()))?;
+                for (k, v) in m {
+                    map.serialize_entry(k, v)?;
+                }
+                map.end()
+            }
+            Value::Number(Number::Float(f)) => serializer.serialize_f64(f.get()),
+            Value::Number(Number::Integer(i)) => serializer.serialize_i64(*i),
+            Value::Option(Some(o)) => serializer.serialize_some(o),
+            Value::Option(None) => serializer.serialize_none(),
+            Value::String(s) => serializer.serialize_str(s),
+            Value::List(l) => {
+                let mut seq = serializer.serialize_seq(Some(l.len()))?;
+                for element in l {
+                    seq.serialize_element(element)?;
+                }
+                seq.end()
+            }
+            Value::Unit(Some(name)) => serialize_named(serializer, name, &()),
+            Value::Unit(None) => serializer.serialize_unit(),
+            Value::Tuple(Some(name), elements) => {
+                serialize_named(serializer, name, &Elements(elements))
+            }
+            Value::Tuple(None, elements) => Elements(elements).serialize(serializer),
+            Value::Struct(Some(name), fields) => serialize_named(serializer, name, &Fields(fields)),
+            Value::Struct(None, fields) => Fields(fields).serialize(serializer),
+        }
+    }
+}
+
+fn serialize_named<S>(serializer: S, name: &str, value: &impl Serialize) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut map = serializer.serialize_map(Some(1))?;
+    map.serialize_entry(name, value)?;
+    map.end()
+}
+
+/// The elements of a tuple, without its name
+struct Elements<'a>(&'a [Value]);
+
+impl Serialize for Elements<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tuple = serializer.serialize_tuple(self.0.len())?;
+        for element in self.0 {
+            tuple.serialize_element(element)?;
+        }
+        tuple.end()
+    }
+}
+
+/// The fields of a struct, without its name
+struct Fields<'a>(&'a [(String, Value)]);
+
+impl Serialize for Fields<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (k, v) in self.0 {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
+
 /// Deserializer implementation for RON `Value`.
 impl<'de> Deserializer<'de> for Value {
     type Error = Error;
@@ -39,8 +126,9 @@ impl<'de> Deserializer<'de> for Value {
             Value::Bool(b) => visitor.visit_bool(b),
             Value::Char(c) => visitor.visit_char(c),
             Value::Map(m) => visitor.visit_map(MapAccessor {
-                keys: m.iter().rev().map(|kv| kv.0.clone()).collect(),
-                values: m.iter().rev().map(|kv| kv.1.clone()).collect(),
+                iter: m.into_iter(),
+                fields: false,
+                value: None,
             }),
             Value::Number(Number::Float(ref f)) => visitor.visit_f64(f.get()),
             Value::Number(Number::Integer(i)) => visitor.visit_i64(i),
@@ -54,6 +142,7 @@ impl<'de> Deserializer<'de> for Value {
             Value::Unit(None) => visitor.visit_unit(),
             Value::List(l) => visitor.visit_seq(Seq {
                 seq: l.into_iter().rev().collect(),
+                index: 0,
             }),
             Value::Tuple(Some(tag), untagged) => visitor.visit_enum(EnumDeserializer {
                 ident: tag,
@@ -61,36 +150,38 @@ impl<'de> Deserializer<'de> for Value {
             }),
             Value::Tuple(None, seq) => visitor.visit_seq(Seq {
                 seq: seq.into_iter().rev().collect(),
+                index: 0,
             }),
             Value::Struct(Some(tag), untagged) => visitor.visit_enum(EnumDeserializer {
                 ident: tag,
                 value: Value::Struct(None, untagged),
             }),
             Value::Struct(None, m) => visitor.visit_map(MapAccessor {
-                keys: m
-                    .iter()
-                    .rev()
-                    .map(|kv| kv.0.clone())
-                    .map(Value::String)
-                    .collect(),
-                values: m.iter().rev().map(|kv| kv.1.clone()).collect(),
+                iter: m
+                    .into_iter()
+                    .map(|(k, v)| (Value::String(k), v))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+                fields: true,
+                value: None,
             }),
         }
     }
 }
 
-struct IdentDeserializer {
-    ident: String,
+/// Borrows the name of a variant, which is kept for the path of errors
+struct IdentDeserializer<'a> {
+    ident: &'a str,
 }
 
-impl<'de> Deserializer<'de> for IdentDeserializer {
+impl<'de> Deserializer<'de> for IdentDeserializer<'_> {
     type Error = crate::error::Error;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_string(self.ident)
+        visitor.visit_str(self.ident)
     }
 
     forward_to_deserialize_any! {
@@ -107,15 +198,66 @@ struct EnumDeserializer {
 
 impl<'de> EnumAccess<'de> for EnumDeserializer {
     type Error = crate::error::Error;
-    type Variant = Value;
+    type Variant = VariantDeserializer;
 
     fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
     where
         V: DeserializeSeed<'de>,
     {
-        let variant_ident = seed.deserialize(IdentDeserializer { ident: self.ident })?;
+        let variant_ident = seed.deserialize(IdentDeserializer { ident: &self.ident })?;
+
+        Ok((
+            variant_ident,
+            VariantDeserializer {
+                name: self.ident,
+                value: self.value,
+            },
+        ))
+    }
+}
+
+/// The content of a variant, adding the variant to the path of errors
+struct VariantDeserializer {
+    name: String,
+    value: Value,
+}
+
+impl VariantDeserializer {
+    fn in_variant<T>(name: String, res: Result<T, Error>) -> Result<T, Error> {
+        res.map_err(|e| e.context_path(PathSegment::Variant(name)))
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Self::in_variant(self.name, self.value.unit_variant())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        Self::in_variant(self.name, self.value.newtype_variant_seed(seed))
+    }
 
-        Ok((variant_ident, self.value))
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Self::in_variant(self.name, self.value.tuple_variant(len, visitor))
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Self::in_variant(self.name, self.value.struct_variant(fields, visitor))
     }
 }
 
@@ -169,8 +311,11 @@ impl<'de> VariantAccess<'de> for Value {
 }
 
 struct MapAccessor {
-    keys: Vec<Value>,
-    values: Vec<Value>,
+    iter: std::vec::IntoIter<(Value, Value)>,
+    /// Whether the keys are the fields of a struct, for the path of errors
+    fields: bool,
+    /// The entry whose key was visited last
+    value: Option<(Value, Value)>,
 }
 
 impl<'de> MapAccess<'de> for MapAccessor {
@@ -180,26 +325,41 @@ impl<'de> MapAccess<'de> for MapAccessor {
     where
         K: DeserializeSeed<'de>,
     {
-        // The `Vec` is reversed, so we can pop to get the originally first element
-        self.keys
-            .pop()
-            .map_or(Ok(None), |v| seed.deserialize(v).map(Some))
+        match self.iter.next() {
+            Some((key, value)) => {
+                // Keys other than strings are cloned, they are usually numbers
+                let res = match &key {
+                    Value::String(s) => seed.deserialize(StrDeserializer::<Error>::new(s)),
+                    key => seed.deserialize(key.clone()),
+                };
+                self.value = Some((key, value));
+
+                res.map(Some)
+            }
+            None => Ok(None),
+        }
     }
 
     fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
     where
         V: DeserializeSeed<'de>,
     {
-        // The `Vec` is reversed, so we can pop to get the originally first element
-        self.values
-            .pop()
-            .map(|v| seed.deserialize(v))
-            .expect("Contract violation")
+        let (key, value) = self.value.take().expect("Contract violation");
+        let fields = self.fields;
+
+        seed.deserialize(value).map_err(|e| {
+            e.context_path(match key {
+                Value::String(name) if fields => PathSegment::Field(name),
+                key => PathSegment::Key(key.to_string()),
+            })
+        })
     }
 }
 
 struct Seq {
     seq: Vec<Value>,
+    /// Index of the next element
+    index: usize,
 }
 
 impl<'de> SeqAccess<'de> for Seq {
@@ -209,10 +369,15 @@ impl<'de> SeqAccess<'de> for Seq {
     where
         T: DeserializeSeed<'de>,
     {
+        let index = self.index;
+        self.index += 1;
+
         // The `Vec` is reversed, so we can pop to get the originally first element
-        self.seq
-            .pop()
-            .map_or(Ok(None), |v| seed.deserialize(v).map(Some))
+        self.seq.pop().map_or(Ok(None), |v| {
+            seed.deserialize(v)
+                .map(Some)
+                .map_err(|e| e.context_path(PathSegment::Index(index)))
+        })
     }
 }
 
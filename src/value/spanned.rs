@@ -0,0 +1,147 @@
+//! Implements AST -> SpannedValue and SpannedValue -> Value
+
+use crate::{
+    ast,
+    ast::{Expr, Spanned, Untagged},
+    value::{Float, Number},
+    Value,
+};
+
+/// Like [`Value`], but every part of the tree remembers where it was defined.
+///
+/// Usually used as `Spanned<SpannedValue>`, which can be deserialized into
+/// Rust types with errors pointing back into the source:
+///
+/// ```
+/// use ron_reboot::{ast::Spanned, value::SpannedValue, Location};
+///
+/// let value: Spanned<SpannedValue> = r#"{ "a": 1, "b": -1 }"#.parse().unwrap();
+/// let e = value.into_rust_serde::<std::collections::HashMap<String, u32>>().unwrap_err();
+///
+/// assert_eq!(e.start(), Some(Location { line: 1, column: 16 }));
+/// ```
+///
+/// Equality ignores locations, see [`Spanned`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpannedValue {
+    Bool(bool),
+    Char(char),
+    Map(Vec<(Spanned<SpannedValue>, Spanned<SpannedValue>)>),
+    Number(Number),
+    Option(Option<Box<Spanned<SpannedValue>>>),
+    String(String),
+    List(Vec<Spanned<SpannedValue>>),
+    Unit(Option<Spanned<String>>),
+    Tuple(Option<Spanned<String>>, Vec<Spanned<SpannedValue>>),
+    Struct(
+        Option<Spanned<String>>,
+        Vec<(Spanned<String>, Spanned<SpannedValue>)>,
+    ),
+}
+
+impl SpannedValue {
+    pub fn from_ast(ast: ast::Ron) -> Spanned<Self> {
+        ast.expr.into()
+    }
+}
+
+#[cfg(feature = "utf8_parser")]
+impl std::str::FromStr for Spanned<SpannedValue> {
+    type Err = crate::Error;
+
+    /// Creates a spanned value from a string reference.
+    fn from_str(s: &str) -> Result<Self, crate::Error> {
+        crate::utf8_parser::ast_from_str(s).map(SpannedValue::from_ast)
+    }
+}
+
+fn ident_to_string(ident: Spanned<ast::Ident>) -> Spanned<String> {
+    Spanned {
+        start: ident.start,
+        value: ident.value.into_string(),
+        end: ident.end,
+    }
+}
+
+fn fields_from_ast(
+    fields: ast::SpannedKvs<ast::Ident>,
+) -> Vec<(Spanned<String>, Spanned<SpannedValue>)> {
+    fields
+        .into_iter()
+        .map(|kv| (ident_to_string(kv.value.key), kv.value.value.into()))
+        .collect()
+}
+
+impl<'a> From<ast::Spanned<ast::Expr<'a>>> for Spanned<SpannedValue> {
+    fn from(e: ast::Spanned<ast::Expr<'a>>) -> Self {
+        let value = match e.value {
+            Expr::Unit => SpannedValue::Unit(None),
+            Expr::Optional(o) => SpannedValue::Option(o.map(|s| Box::new((*s).into()))),
+            Expr::Tagged(ast::Tagged { ident, untagged }) => match untagged.value {
+                Untagged::Unit => SpannedValue::Unit(Some(ident_to_string(ident))),
+                Untagged::Struct(s) => {
+                    SpannedValue::Struct(Some(ident_to_string(ident)), fields_from_ast(s.fields))
+                }
+                Untagged::Tuple(t) => SpannedValue::Tuple(
+                    Some(ident_to_string(ident)),
+                    t.elements.into_iter().map(Into::into).collect(),
+                ),
+            },
+            Expr::Bool(b) => SpannedValue::Bool(b),
+            Expr::Tuple(t) => {
+                SpannedValue::Tuple(None, t.elements.into_iter().map(Into::into).collect())
+            }
+            Expr::List(l) => SpannedValue::List(l.elements.into_iter().map(Into::into).collect()),
+            Expr::Map(m) => SpannedValue::Map(
+                m.entries
+                    .into_iter()
+                    .map(|kv| (kv.value.key.into(), kv.value.value.into()))
+                    .collect(),
+            ),
+            Expr::Struct(s) => SpannedValue::Struct(None, fields_from_ast(s.fields)),
+            Expr::Integer(i) => SpannedValue::Number(Number::Integer(i.into_i64())),
+            Expr::Str(s) => SpannedValue::String(s.to_owned()),
+            Expr::String(s) => SpannedValue::String(s),
+            Expr::Char(c) => SpannedValue::Char(c),
+            Expr::Decimal(d) => SpannedValue::Number(Number::Float(Float::new(d.into()))),
+            Expr::NonFinite(n) => SpannedValue::Number(Number::Float(Float::new(n.into()))),
+            Expr::UnquotedKey(k) => SpannedValue::String(k.into_string()),
+        };
+
+        Spanned {
+            start: e.start,
+            value,
+            end: e.end,
+        }
+    }
+}
+
+/// Drops all locations
+impl From<Spanned<SpannedValue>> for Value {
+    fn from(v: Spanned<SpannedValue>) -> Self {
+        let tag = |t: Option<Spanned<String>>| t.map(|t| t.value);
+
+        match v.value {
+            SpannedValue::Bool(b) => Value::Bool(b),
+            SpannedValue::Char(c) => Value::Char(c),
+            SpannedValue::Map(m) => {
+                Value::Map(m.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+            }
+            SpannedValue::Number(n) => Value::Number(n),
+            SpannedValue::Option(o) => Value::Option(o.map(|v| Box::new((*v).into()))),
+            SpannedValue::String(s) => Value::String(s),
+            SpannedValue::List(l) => Value::List(l.into_iter().map(Into::into).collect()),
+            SpannedValue::Unit(t) => Value::Unit(tag(t)),
+            SpannedValue::Tuple(t, elements) => {
+                Value::Tuple(tag(t), elements.into_iter().map(Into::into).collect())
+            }
+            SpannedValue::Struct(t, fields) => Value::Struct(
+                tag(t),
+                fields
+                    .into_iter()
+                    .map(|(k, v)| (k.value, v.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
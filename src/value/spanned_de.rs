@@ -0,0 +1,463 @@
+//! Deserializer for `Spanned<SpannedValue>`, which locates errors
+
+use serde::{
+    de::{
+        DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, Error as SerdeError,
+        MapAccess, SeqAccess, VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+};
+
+use crate::{
+    ast::Spanned,
+    spanned::{self, SpannedDeserializer},
+    value::{Number, SpannedValue, Value},
+    Error, Location, PathSegment,
+};
+
+impl Spanned<SpannedValue> {
+    /// Tries to deserialize this value into `T`.
+    ///
+    /// Unlike [`crate::Value::into_rust_serde`], errors point to the part of the value
+    /// which could not be deserialized.
+    pub fn into_rust_serde<T>(self) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        T::deserialize(self)
+    }
+
+    fn untag(self) -> (Option<Spanned<String>>, Self) {
+        let (tag, value) = match self.value {
+            SpannedValue::Unit(tag) => (tag, SpannedValue::Unit(None)),
+            SpannedValue::Tuple(tag, elements) => (tag, SpannedValue::Tuple(None, elements)),
+            SpannedValue::Struct(tag, fields) => (tag, SpannedValue::Struct(None, fields)),
+            x => (None, x),
+        };
+
+        (
+            tag,
+            Spanned {
+                start: self.start,
+                value,
+                end: self.end,
+            },
+        )
+    }
+}
+
+fn string_key(key: Spanned<String>) -> Spanned<SpannedValue> {
+    Spanned {
+        start: key.start,
+        value: SpannedValue::String(key.value),
+        end: key.end,
+    }
+}
+
+fn check_tag(tag: &Option<Spanned<String>>, kind: &str, name: &str) -> Result<(), Error> {
+    match tag {
+        Some(tag) if tag.value != name => Err(Error::custom(format!(
+            "invalid {} type: `{}`, expected `{}`",
+            kind, tag.value, name
+        ))
+        .context_loc(tag.start, tag.end)),
+        _ => Ok(()),
+    }
+}
+
+impl<'de> Deserializer<'de> for Spanned<SpannedValue> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let (start, end) = (self.start, self.end);
+
+        let res = match self.value {
+            SpannedValue::Bool(b) => visitor.visit_bool(b),
+            SpannedValue::Char(c) => visitor.visit_char(c),
+            SpannedValue::Map(m) => visitor.visit_map(MapAccessor {
+                iter: m.into_iter(),
+                fields: false,
+                value: None,
+            }),
+            SpannedValue::Number(Number::Float(f)) => visitor.visit_f64(f.get()),
+            SpannedValue::Number(Number::Integer(i)) => visitor.visit_i64(i),
+            SpannedValue::Option(Some(o)) => visitor.visit_some(*o),
+            SpannedValue::Option(None) => visitor.visit_none(),
+            SpannedValue::String(s) => visitor.visit_string(s),
+            SpannedValue::Unit(Some(tag)) => visitor.visit_string(tag.value),
+            SpannedValue::Unit(None) => visitor.visit_unit(),
+            SpannedValue::List(elements) | SpannedValue::Tuple(_, elements) => {
+                visitor.visit_seq(Seq {
+                    iter: elements.into_iter(),
+                    index: 0,
+                })
+            }
+            SpannedValue::Struct(_, fields) => visitor.visit_map(MapAccessor {
+                iter: fields
+                    .into_iter()
+                    .map(|(k, v)| (string_key(k), v))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+                fields: true,
+                value: None,
+            }),
+        };
+
+        res.map_err(|e| e.context_loc(start, end))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if name == spanned::NAME && fields == spanned::FIELDS {
+            let (start, end) = (self.start, self.end);
+
+            return visitor.visit_map(SpannedDeserializer::new(start, end, self));
+        }
+
+        if let SpannedValue::Struct(tag, _) = &self.value {
+            check_tag(tag, "struct", name)?;
+        }
+
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let (start, end) = (self.start, self.end);
+
+        match self.untag() {
+            (Some(tag), untagged) => visitor
+                .visit_enum(EnumAccessor { tag, untagged })
+                .map_err(|e| e.context_loc(start, end)),
+            // probably no enum and will error
+            (None, value) => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Spanned {
+                value: SpannedValue::Option(Some(o)),
+                ..
+            } => visitor.visit_some(*o),
+            Spanned {
+                value: SpannedValue::Option(None),
+                ..
+            } => visitor.visit_none(),
+            this => this.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Spanned {
+                value: SpannedValue::Tuple(tag, mut elements),
+                ..
+            } if elements.len() == 1 => {
+                check_tag(&tag, "newtype struct", name)?;
+
+                visitor.visit_newtype_struct(elements.remove(0))
+            }
+            this => this.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map identifier
+    }
+}
+
+struct EnumAccessor {
+    tag: Spanned<String>,
+    untagged: Spanned<SpannedValue>,
+}
+
+impl<'de> EnumAccess<'de> for EnumAccessor {
+    type Error = Error;
+    type Variant = VariantAccessor;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant_ident = seed.deserialize(StrKey {
+            start: self.tag.start,
+            value: &self.tag.value,
+            end: self.tag.end,
+        })?;
+
+        Ok((
+            variant_ident,
+            VariantAccessor {
+                name: self.tag.value,
+                untagged: self.untagged,
+            },
+        ))
+    }
+}
+
+/// The content of a variant, adding the variant to the path of errors
+struct VariantAccessor {
+    name: String,
+    untagged: Spanned<SpannedValue>,
+}
+
+impl VariantAccessor {
+    fn in_variant<T>(name: String, res: Result<T, Error>) -> Result<T, Error> {
+        res.map_err(|e| e.context_path(PathSegment::Variant(name)))
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantAccessor {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Self::in_variant(self.name, self.untagged.unit_variant())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        Self::in_variant(self.name, self.untagged.newtype_variant_seed(seed))
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Self::in_variant(self.name, self.untagged.tuple_variant(len, visitor))
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Self::in_variant(self.name, self.untagged.struct_variant(fields, visitor))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Spanned<SpannedValue> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match &self.value {
+            SpannedValue::Unit(None) => Ok(()),
+            _ => Err(Error::custom("expected unit variant").context_loc(self.start, self.end)),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.value {
+            SpannedValue::Tuple(None, mut elements) if elements.len() == 1 => {
+                seed.deserialize(elements.remove(0))
+            }
+            _ => Err(Error::custom("expected newtype variant").context_loc(self.start, self.end)),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match &self.value {
+            SpannedValue::Tuple(None, _) => self.deserialize_any(visitor),
+            _ => Err(Error::custom("expected tuple variant").context_loc(self.start, self.end)),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match &self.value {
+            SpannedValue::Struct(None, _) => self.deserialize_any(visitor),
+            _ => Err(Error::custom("expected struct variant").context_loc(self.start, self.end)),
+        }
+    }
+}
+
+struct MapAccessor {
+    iter: std::vec::IntoIter<(Spanned<SpannedValue>, Spanned<SpannedValue>)>,
+    /// Whether the keys are the fields of a struct, for the path of errors
+    fields: bool,
+    /// The entry whose key was visited last
+    value: Option<(Spanned<SpannedValue>, Spanned<SpannedValue>)>,
+}
+
+impl<'de> MapAccess<'de> for MapAccessor {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                let res = deserialize_key(seed, &key);
+                self.value = Some((key, value));
+
+                res.map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (key, value) = self
+            .value
+            .take()
+            .expect("called next_value_seed before next_key_seed");
+        let fields = self.fields;
+
+        seed.deserialize(value).map_err(|e| {
+            e.context_path(match key.value {
+                SpannedValue::String(name) if fields => PathSegment::Field(name),
+                _ => PathSegment::Key(Value::from(key).to_string()),
+            })
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Deserializes `key` without giving it up, so it's still there for the path of errors
+///
+/// Only keys other than strings are cloned, which are usually numbers.
+fn deserialize_key<'de, K>(seed: K, key: &Spanned<SpannedValue>) -> Result<K::Value, Error>
+where
+    K: DeserializeSeed<'de>,
+{
+    match &key.value {
+        SpannedValue::String(value) => seed.deserialize(StrKey {
+            start: key.start,
+            value,
+            end: key.end,
+        }),
+        _ => seed.deserialize(key.clone()),
+    }
+}
+
+/// A borrowed string key or tag, which can be deserialized as `Spanned`
+struct StrKey<'a> {
+    start: Location,
+    value: &'a str,
+    end: Location,
+}
+
+impl<'de> Deserializer<'de> for StrKey<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor
+            .visit_str::<Error>(self.value)
+            .map_err(|e| e.context_loc(self.start, self.end))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if name == spanned::NAME && fields == spanned::FIELDS {
+            let (start, end) = (self.start, self.end);
+
+            return visitor.visit_map(SpannedDeserializer::new(start, end, self));
+        }
+
+        self.deserialize_any(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+struct Seq {
+    iter: std::vec::IntoIter<Spanned<SpannedValue>>,
+    /// Index of the next element
+    index: usize,
+}
+
+impl<'de> SeqAccess<'de> for Seq {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let index = self.index;
+        self.index += 1;
+
+        self.iter.next().map_or(Ok(None), |v| {
+            seed.deserialize(v)
+                .map(Some)
+                .map_err(|e| e.context_path(PathSegment::Index(index)))
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
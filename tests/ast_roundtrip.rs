@@ -0,0 +1,268 @@
+use std::fmt::Write;
+
+use proptest::prelude::*;
+use ron_reboot::{
+    ast::{
+        Decimal, Expr, Ident, Integer, KeyValue, List, Map, NonFinite, Sign, SignedInteger,
+        Spanned, Struct, Tagged, Tuple, UnsignedInteger, Untagged,
+    },
+    utf8_parser::ast_from_str,
+    Location,
+};
+
+/// Spans are not compared, so any location will do
+fn spanned<T>(value: T) -> Spanned<T> {
+    let location = Location { line: 1, column: 1 };
+
+    Spanned {
+        start: location,
+        value,
+        end: location,
+    }
+}
+
+/// `Expr::Str` borrows from the input, the generated strings live until the end of the test
+fn leak(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
+
+fn sign() -> impl Strategy<Value = Sign> {
+    prop_oneof![Just(Sign::Positive), Just(Sign::Negative)]
+}
+
+fn decimal() -> impl Strategy<Value = Decimal> {
+    let fractional =
+        (1..=19u16).prop_flat_map(|digits| (0..10u64.pow(digits as u32), Just(digits)));
+    let exponent = proptest::option::of((proptest::option::of(sign()), any::<u16>()));
+
+    // A sign is only allowed in front of a whole part (`-1.5`, not `-.5`)
+    let sign_whole = prop_oneof![
+        Just((None, None)),
+        (proptest::option::of(sign()), any::<u64>()).prop_map(|(sign, whole)| (sign, Some(whole))),
+    ];
+
+    (sign_whole, fractional, exponent).prop_map(
+        |((sign, whole), (fractional, fractional_digits), exponent)| {
+            Decimal::new(sign, whole, fractional, fractional_digits, exponent)
+        },
+    )
+}
+
+/// Identifiers of tagged expressions, which must not be mistaken for keywords
+fn tag_ident() -> impl Strategy<Value = Ident<'static>> {
+    "[A-Z_][A-Za-z0-9_]{0,8}"
+        .prop_filter("Some / None are options, NaN a float", |s| {
+            s != "Some" && s != "None" && s != "NaN"
+        })
+        .prop_map(|s| Ident(leak(s)))
+}
+
+fn field_ident() -> impl Strategy<Value = Ident<'static>> {
+    "[a-z_][a-z0-9_]{0,8}".prop_map(|s| Ident(leak(s)))
+}
+
+fn leaf() -> impl Strategy<Value = Expr<'static>> {
+    // Strings without escapes are parsed as `Str`, so `String` needs at least one
+    let escaped = ("[^\"\\\\]*", "[\"\\\\]", "[^\"\\\\]*").prop_map(|(a, b, c)| a + &b + &c);
+
+    prop_oneof![
+        Just(Expr::Unit),
+        Just(Expr::Optional(None)),
+        any::<bool>().prop_map(Expr::Bool),
+        any::<u64>().prop_map(|n| Expr::Integer(Integer::Unsigned(UnsignedInteger { number: n }))),
+        (sign(), any::<u64>()).prop_map(|(sign, number)| {
+            Expr::Integer(Integer::Signed(SignedInteger { sign, number }))
+        }),
+        decimal().prop_map(Expr::Decimal),
+        prop_oneof![
+            proptest::option::of(sign()).prop_map(NonFinite::Infinity),
+            Just(NonFinite::NaN),
+        ]
+        .prop_map(Expr::NonFinite),
+        "[^\"\\\\]*".prop_map(|s| Expr::Str(leak(s))),
+        escaped.prop_map(Expr::String),
+        any::<char>().prop_map(Expr::Char),
+        tag_ident().prop_map(|ident| Expr::Tagged(Tagged {
+            ident: spanned(ident),
+            untagged: spanned(Untagged::Unit),
+        })),
+    ]
+}
+
+fn expr() -> impl Strategy<Value = Expr<'static>> {
+    leaf().prop_recursive(4, 64, 6, |inner| {
+        let elements = |min| proptest::collection::vec(inner.clone().prop_map(spanned), min..6);
+        let fields = |min| {
+            proptest::collection::vec(
+                (field_ident(), inner.clone()).prop_map(|(key, value)| {
+                    spanned(KeyValue {
+                        key: spanned(key),
+                        value: spanned(value),
+                    })
+                }),
+                min..6,
+            )
+        };
+        let entries = proptest::collection::vec(
+            (inner.clone(), inner.clone()).prop_map(|(key, value)| {
+                spanned(KeyValue {
+                    key: spanned(key),
+                    value: spanned(value),
+                })
+            }),
+            0..6,
+        );
+        let untagged = prop_oneof![
+            fields(1).prop_map(|fields| Untagged::Struct(Struct { fields })),
+            elements(1).prop_map(|elements| Untagged::Tuple(Tuple { elements })),
+        ];
+
+        prop_oneof![
+            inner
+                .clone()
+                .prop_map(|e| Expr::Optional(Some(Box::new(spanned(e))))),
+            (tag_ident(), untagged).prop_map(|(ident, untagged)| Expr::Tagged(Tagged {
+                ident: spanned(ident),
+                untagged: spanned(untagged),
+            })),
+            // `()` is the unit value, not an empty tuple or struct
+            elements(1).prop_map(|elements| Expr::Tuple(Tuple { elements })),
+            fields(1).prop_map(|fields| Expr::Struct(Struct { fields })),
+            elements(0).prop_map(|elements| Expr::List(List { elements })),
+            entries.prop_map(|entries| Expr::Map(Map { entries })),
+        ]
+    })
+}
+
+fn print_sign(out: &mut String, sign: Sign) {
+    out.push(match sign {
+        Sign::Positive => '+',
+        Sign::Negative => '-',
+    });
+}
+
+fn print_list<'a, T: 'a>(
+    out: &mut String,
+    (open, close): (char, char),
+    items: impl IntoIterator<Item = &'a T>,
+    mut print_item: impl FnMut(&mut String, &T),
+) {
+    out.push(open);
+    for item in items {
+        print_item(out, item);
+        out.push_str(", ");
+    }
+    out.push(close);
+}
+
+fn print_fields(out: &mut String, fields: &[Spanned<KeyValue<Ident>>]) {
+    print_list(out, ('(', ')'), fields, |out, kv| {
+        write!(out, "{}: ", kv.value.key.value.0).unwrap();
+        print(out, &kv.value.value.value);
+    });
+}
+
+fn print_elements(out: &mut String, delimiters: (char, char), elements: &[Spanned<Expr>]) {
+    print_list(out, delimiters, elements, |out, e| print(out, &e.value));
+}
+
+fn print(out: &mut String, expr: &Expr) {
+    match expr {
+        Expr::Unit => out.push_str("()"),
+        Expr::Optional(None) => out.push_str("None"),
+        Expr::Optional(Some(e)) => {
+            out.push_str("Some(");
+            print(out, &e.value);
+            out.push(')');
+        }
+        Expr::Tagged(Tagged { ident, untagged }) => {
+            out.push_str(ident.value.0);
+            match &untagged.value {
+                Untagged::Unit => {}
+                Untagged::Struct(s) => print_fields(out, &s.fields),
+                Untagged::Tuple(t) => print_elements(out, ('(', ')'), &t.elements),
+            }
+        }
+        Expr::UnquotedKey(k) => out.push_str(k.0),
+        Expr::Bool(b) => write!(out, "{}", b).unwrap(),
+        Expr::Tuple(t) => print_elements(out, ('(', ')'), &t.elements),
+        Expr::List(l) => print_elements(out, ('[', ']'), &l.elements),
+        Expr::Map(m) => print_list(out, ('{', '}'), &m.entries, |out, kv| {
+            print(out, &kv.value.key.value);
+            out.push_str(": ");
+            print(out, &kv.value.value.value);
+        }),
+        Expr::Struct(s) => print_fields(out, &s.fields),
+        Expr::Integer(Integer::Unsigned(u)) => write!(out, "{}", u.number).unwrap(),
+        Expr::Integer(Integer::Signed(s)) => {
+            print_sign(out, s.sign);
+            write!(out, "{}", s.number).unwrap();
+        }
+        Expr::Str(s) => write!(out, "\"{}\"", s).unwrap(),
+        Expr::String(s) => {
+            out.push('"');
+            for c in s.chars() {
+                if c == '"' || c == '\\' {
+                    out.push('\\');
+                }
+                out.push(c);
+            }
+            out.push('"');
+        }
+        Expr::Char(c) => match c {
+            '\'' | '\\' => write!(out, "'\\{}'", c).unwrap(),
+            c => write!(out, "'{}'", c).unwrap(),
+        },
+        Expr::NonFinite(NonFinite::Infinity(sign)) => {
+            if let Some(sign) = sign {
+                print_sign(out, *sign);
+            }
+            out.push_str("inf");
+        }
+        Expr::NonFinite(NonFinite::NaN) => out.push_str("NaN"),
+        Expr::Decimal(d) => {
+            if let Some(sign) = d.sign {
+                print_sign(out, sign);
+            }
+            if let Some(whole) = d.whole {
+                write!(out, "{}", whole).unwrap();
+            }
+            write!(
+                out,
+                ".{:0width$}",
+                d.fractional,
+                width = d.fractional_digits as usize
+            )
+            .unwrap();
+            if let Some((sign, exponent)) = d.exponent {
+                out.push('e');
+                if let Some(sign) = sign {
+                    print_sign(out, sign);
+                }
+                write!(out, "{}", exponent).unwrap();
+            }
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn print_parse_roundtrip(expr in expr()) {
+        let mut printed = String::new();
+        print(&mut printed, &expr);
+
+        let parsed = ast_from_str(&printed).map_err(|e| TestCaseError::fail(e.to_string()))?;
+        prop_assert_eq!(parsed.expr.value, expr, "printed as {}", printed);
+    }
+
+    #[test]
+    fn parse_never_panics(input in "\\PC*") {
+        let _ = ast_from_str(&input);
+    }
+
+    /// Mostly RON syntax, to get past the first few characters
+    #[test]
+    fn parse_ron_like_never_panics(input in "[-+.:,#!()\\[\\]{}\"\\\\/* \n_a-zA-Z0-9]{0,64}") {
+        let _ = ast_from_str(&input);
+    }
+}
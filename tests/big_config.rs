@@ -35,5 +35,5 @@ pub struct Data {
 
 #[test]
 fn big_config() {
-    unwrap_display(from_str_serde::<Config>(INPUT));
+    unwrap_display(INPUT, from_str_serde::<Config>);
 }
@@ -1,4 +1,4 @@
-use ron_reboot::print_error;
+use ron_reboot::Files;
 
 const INPUT: &str = r#"(
     // This is a UUID
@@ -34,10 +34,13 @@ const INPUT: &str = r#"(
 
 #[test]
 fn test_comments() {
-    match ron_reboot::utf8_parser::ast_from_str(INPUT) {
+    let mut files = Files::new();
+    let id = files.add("comments.ron", INPUT);
+
+    match ron_reboot::utf8_parser::ast_from_str(files.source(id)) {
         Ok(_) => {}
         Err(e) => {
-            print_error(&e).unwrap();
+            files.print_error(&e.context_file(id)).unwrap();
             panic!();
         }
     }
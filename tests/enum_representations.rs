@@ -0,0 +1,145 @@
+#![cfg(test)]
+
+use ron_reboot::{
+    utf8_parser::{
+        serde::{from_str_with_options, Options, TaggedMode},
+        test_util::unwrap_display,
+    },
+    Error,
+};
+use serde::Deserialize;
+
+fn from_str_tagged<'a, T: Deserialize<'a>>(s: &'a str, mode: TaggedMode) -> Result<T, Error> {
+    from_str_with_options(s, Options::default().tagged(mode))
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum External {
+    Circle { radius: f64 },
+    Point,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+enum Internal {
+    Circle { radius: f64 },
+    Point,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "type", content = "data")]
+enum Adjacent {
+    Circle { radius: f64 },
+    Scaled(f64),
+    Pair(u8, u8),
+    Point,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum Untagged {
+    Circle { radius: f64 },
+    Point,
+}
+
+#[test]
+fn externally_tagged() {
+    for mode in [
+        TaggedMode::Ignore,
+        TaggedMode::Field("type"),
+        TaggedMode::Adjacent("type", "data"),
+    ] {
+        assert_eq!(
+            unwrap_display("[Circle(radius: 1.0), Point]", |s| {
+                from_str_tagged::<Vec<External>>(s, mode)
+            }),
+            vec![External::Circle { radius: 1.0 }, External::Point]
+        );
+    }
+}
+
+#[test]
+fn internally_tagged() {
+    assert_eq!(
+        unwrap_display(
+            r#"[Circle(radius: 1.0), Point, (type: "Circle", radius: 2.0)]"#,
+            |s| from_str_tagged::<Vec<Internal>>(s, TaggedMode::Field("type"))
+        ),
+        vec![
+            Internal::Circle { radius: 1.0 },
+            Internal::Point,
+            Internal::Circle { radius: 2.0 },
+        ]
+    );
+
+    // the tag must not be given twice
+    let e = from_str_tagged::<Internal>(
+        r#"Circle(type: "Circle", radius: 1.0)"#,
+        TaggedMode::Field("type"),
+    )
+    .unwrap_err();
+    assert_eq!(e.to_string(), "error at 1:8: duplicate field `type`");
+    assert!(from_str_tagged::<Internal>("Circle(radius: 1.0)", TaggedMode::Ignore).is_err());
+}
+
+#[test]
+fn adjacently_tagged() {
+    assert_eq!(
+        unwrap_display(
+            r#"[
+                Circle(radius: 1.0),
+                Scaled(0.5),
+                Pair(1, 2),
+                Point,
+                (type: Scaled, data: 2.0),
+            ]"#,
+            |s| from_str_tagged::<Vec<Adjacent>>(s, TaggedMode::Adjacent("type", "data"))
+        ),
+        vec![
+            Adjacent::Circle { radius: 1.0 },
+            Adjacent::Scaled(0.5),
+            Adjacent::Pair(1, 2),
+            Adjacent::Point,
+            Adjacent::Scaled(2.0),
+        ]
+    );
+
+    let e = from_str_tagged::<Adjacent>("Scald(0.5)", TaggedMode::Adjacent("type", "data"))
+        .unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "error at 1:1 in `type`: unknown variant `Scald`, expected one of `Circle`, `Scaled`, `Pair`, `Point`"
+    );
+
+    // other tag and content fields are not mistaken for the enum
+    for mode in [
+        TaggedMode::Field("type"),
+        TaggedMode::Adjacent("kind", "data"),
+        TaggedMode::Adjacent("type", "content"),
+    ] {
+        assert!(from_str_tagged::<Adjacent>("Scaled(0.5)", mode).is_err());
+    }
+
+    // `deserialize_any` splits the name and the content as well
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(untagged)]
+    enum Shape {
+        Adjacent(Adjacent),
+    }
+    assert_eq!(
+        unwrap_display("Pair(1, 2)", |s| {
+            from_str_tagged::<Shape>(s, TaggedMode::Adjacent("type", "data"))
+        }),
+        Shape::Adjacent(Adjacent::Pair(1, 2))
+    );
+}
+
+#[test]
+fn untagged() {
+    assert_eq!(
+        unwrap_display(r#"[Circle(radius: 1.0), ()]"#, |s| {
+            from_str_tagged::<Vec<Untagged>>(s, TaggedMode::Field("type"))
+        }),
+        vec![Untagged::Circle { radius: 1.0 }, Untagged::Point]
+    );
+}
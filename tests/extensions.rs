@@ -1,4 +1,14 @@
-use ron_reboot::from_str_serde;
+use std::collections::HashMap;
+
+use ron_reboot::{
+    ast::{Expr, Ident},
+    from_str_serde,
+    utf8_parser::{
+        ast_from_str,
+        serde::{from_str_with_options, Options},
+    },
+    Location,
+};
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize, PartialEq)]
@@ -163,3 +173,160 @@ fn both_implicit_some_unwrap_newtypes() {
         log: None,
     });
 }
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Window {
+    title: String,
+    size: (u32, u32),
+    position: Option<Position>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn test_implicit_outer_struct() {
+    assert_eq!(
+        from_str_serde::<Window>(
+            r#"
+#![enable(implicit_outer_struct, implicit_some)]
+
+title: "Editor",
+size: (800, 600),
+position: (x: 10, y: -20),
+    "#
+        )
+        .unwrap(),
+        Window {
+            title: "Editor".to_owned(),
+            size: (800, 600),
+            position: Some(Position { x: 10, y: -20 }),
+        }
+    );
+
+    // The parentheses are still allowed
+    assert_eq!(
+        from_str_serde::<Position>(
+            r#"
+#![enable(implicit_outer_struct)]
+
+(x: 1, y: 2)
+    "#
+        )
+        .unwrap(),
+        Position { x: 1, y: 2 }
+    );
+
+    let options = Options::default().implicit_outer_struct(true);
+    assert_eq!(
+        from_str_with_options::<Position>("x: 1, y: 2", options).unwrap(),
+        Position { x: 1, y: 2 }
+    );
+
+    let ron = ast_from_str("#![enable(implicit_outer_struct)]\nx: 1,\ny: 2,\n").unwrap();
+    assert_eq!(ron.expr.start, Location { line: 2, column: 1 });
+    assert_eq!(ron.expr.end, Location { line: 3, column: 6 });
+}
+
+#[test]
+fn implicit_outer_struct_missing() {
+    let e = from_str_serde::<Position>("x: 1,\ny: 2").unwrap_err();
+
+    assert_eq!(e.start(), Some(Location { line: 1, column: 2 }));
+    assert_eq!(
+        e.notes()
+            .iter()
+            .map(|n| n.message.as_str())
+            .collect::<Vec<_>>(),
+        [
+            "the document looks like the fields of a struct without `(` and `)`",
+            "put `(` and `)` around the fields or add `#![enable(implicit_outer_struct)]`",
+        ]
+    );
+
+    // Other errors don't mention the extension
+    let e = from_str_serde::<Position>("(x: 1,\ny: 2").unwrap_err();
+    assert!(e.notes().is_empty());
+}
+
+#[derive(Debug, Deserialize, Eq, Hash, PartialEq)]
+enum Level {
+    Debug,
+    Info,
+}
+
+#[test]
+fn test_unquoted_map_keys() {
+    assert_eq!(
+        from_str_serde::<HashMap<String, u32>>(
+            r#"
+#![enable(unquoted_map_keys)]
+
+{
+    width: 800,
+    r#type: 1,
+    "quoted keys": 2,
+}
+    "#
+        )
+        .unwrap(),
+        vec![
+            ("width".to_owned(), 800),
+            ("type".to_owned(), 1),
+            ("quoted keys".to_owned(), 2),
+        ]
+        .into_iter()
+        .collect()
+    );
+
+    // Unit variants still work as keys
+    assert_eq!(
+        from_str_serde::<HashMap<Level, bool>>(
+            r#"
+#![enable(unquoted_map_keys)]
+
+{Debug: false, Info: true}
+    "#
+        )
+        .unwrap(),
+        vec![(Level::Debug, false), (Level::Info, true)]
+            .into_iter()
+            .collect()
+    );
+
+    let options = Options::default().unquoted_map_keys(true);
+    let e = from_str_with_options::<HashMap<String, u8>>("{size: -1}", options).unwrap_err();
+    assert_eq!(e.path().unwrap().to_string(), r#"["size"]"#);
+
+    // Unquoted and quoted keys deserialize to the same string
+    let e = from_str_with_options::<HashMap<String, u8>>(r#"{a: 1, "a": 2}"#, options).unwrap_err();
+    assert_eq!(e.to_string(), "error at 1:8: duplicate map key");
+    assert_eq!(e.labels()[0].start, Location { line: 1, column: 2 });
+}
+
+#[test]
+fn unquoted_map_keys_ast() {
+    fn key(source: &str) -> Expr<'_> {
+        match ast_from_str(source).unwrap().expr.value {
+            Expr::Map(mut m) => m.entries.remove(0).value.key.value,
+            _ => unreachable!(),
+        }
+    }
+
+    assert_eq!(
+        key("#![enable(unquoted_map_keys)]\n{name: 1}"),
+        Expr::UnquotedKey(Ident("name"))
+    );
+    assert_eq!(
+        key("#![enable(unquoted_map_keys)]\n{true: 1}"),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        key("#![enable(unquoted_map_keys)]\n{None: 1}"),
+        Expr::Optional(None)
+    );
+    assert!(matches!(key("{name: 1}"), Expr::Tagged(_)));
+}
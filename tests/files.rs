@@ -0,0 +1,75 @@
+use ron_reboot::{from_str_serde, utf8_parser::ast_from_str, Files, Location};
+
+fn render(files: &Files, e: &ron_reboot::Error) -> String {
+    let mut rendered = vec![];
+    files.write_error(&mut rendered, e).unwrap();
+
+    String::from_utf8(rendered).unwrap()
+}
+
+#[test]
+fn errors_reference_files() {
+    let mut files = Files::new();
+    let id = files.add("config.ron", "(\n    x: true,\n)");
+
+    let e = from_str_serde::<bool>(files.source(id))
+        .unwrap_err()
+        .context_file(id);
+    assert_eq!(e.file_id(), Some(id));
+    assert_eq!(e.context.as_ref().unwrap().file_content, None);
+
+    assert_eq!(
+        render(&files, &e),
+        "error: invalid type: map, expected a boolean
+ --> config.ron:1:1
+  |
+1 |   (
+  |  ^
+2 | |     x: true,
+3 | | )
+  | |_^
+  |
+"
+    );
+}
+
+#[test]
+fn labels_in_other_files() {
+    let mut files = Files::new();
+    let main = files.add("main.ron", "(\n    child: include(\"child.ron\"),\n)");
+    let child = files.add("child.ron", "[1, 2");
+
+    let e = ast_from_str(files.source(child))
+        .unwrap_err()
+        .context_file(child)
+        .context_label_in(
+            main,
+            Location {
+                line: 2,
+                column: 12,
+            },
+            Location {
+                line: 2,
+                column: 33,
+            },
+            "included here",
+        );
+
+    assert_eq!(
+        render(&files, &e),
+        format!(
+            "error: {}
+ --> child.ron:1:6
+  |
+1 | [1, 2
+  |      ^
+ ::: main.ron:2:12
+  |
+2 |     child: include(\"child.ron\"),
+  |            --------------------- included here
+  |
+",
+            e.kind
+        )
+    );
+}
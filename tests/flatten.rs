@@ -16,7 +16,7 @@ struct MyStruct {
 #[test]
 fn flattened_struct_support() {
     assert_eq!(
-        unwrap_display(from_str_serde::<MyStruct>(
+        unwrap_display(
             r#"
 (
     foo: false,
@@ -24,8 +24,9 @@ fn flattened_struct_support() {
 
     extension_baz: true,
 )
-        "#
-        )),
+        "#,
+            from_str_serde::<MyStruct>
+        ),
         MyStruct {
             foo: false,
             bar: "bar".to_string(),
@@ -46,7 +47,7 @@ enum MyEnum {
 #[test]
 fn untagged_enum_support() {
     assert_eq!(
-        unwrap_display(from_str_serde::<MyEnum>(
+        unwrap_display(
             r#"
 MyStruct(
     foo: false,
@@ -54,8 +55,9 @@ MyStruct(
 
     extension_baz: true,
 )
-        "#
-        )),
+        "#,
+            from_str_serde::<MyEnum>
+        ),
         MyEnum::MyStruct(MyStruct {
             foo: false,
             bar: "bar".to_string(),
@@ -66,11 +68,12 @@ MyStruct(
     );
 
     assert_eq!(
-        unwrap_display(from_str_serde::<MyEnum>(
+        unwrap_display(
             r#"
 false
-        "#
-        )),
+        "#,
+            from_str_serde::<MyEnum>
+        ),
         MyEnum::Bool(false)
     );
 }
@@ -85,7 +88,7 @@ enum TypeTagged {
 #[test]
 fn adjacently_tagged_enum_support() {
     assert_eq!(
-        unwrap_display(from_str_serde::<TypeTagged>(
+        unwrap_display(
             r#"
 (
     t: MyStruct,
@@ -96,8 +99,9 @@ fn adjacently_tagged_enum_support() {
         extension_baz: true,
     )
 )
-        "#
-        )),
+        "#,
+            from_str_serde::<TypeTagged>
+        ),
         TypeTagged::MyStruct(MyStruct {
             foo: false,
             bar: "bar".to_string(),
@@ -108,14 +112,15 @@ fn adjacently_tagged_enum_support() {
     );
 
     assert_eq!(
-        unwrap_display(from_str_serde::<TypeTagged>(
+        unwrap_display(
             r#"
 (
     t: Bool,
     c: false
 )
-        "#
-        )),
+        "#,
+            from_str_serde::<TypeTagged>
+        ),
         TypeTagged::Bool(false)
     );
 }
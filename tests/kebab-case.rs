@@ -33,3 +33,13 @@ fn roundtrip_ident_with_dash() {
 
     assert_eq!(Ok(value), deserial);
 }
+
+#[test]
+fn suggest_kebab_case_names() {
+    let e = from_str_serde::<MyEnumWithDashes>("ThisIsMyUnitVariant").unwrap_err();
+
+    assert_eq!(
+        e.notes()[0].message,
+        "did you mean `this-is-my-unit-variant`?"
+    );
+}
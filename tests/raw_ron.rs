@@ -0,0 +1,165 @@
+use ron_reboot::{
+    from_str_serde,
+    utf8_parser::{
+        ast_from_str,
+        serde::{from_reader, RawRon, RawRonBuf, RonDeserializer},
+    },
+    Files, Location,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Plugin<'a> {
+    r#type: &'a str,
+    #[serde(borrow)]
+    config: RawRon<'a>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Logger<'a> {
+    level: u8,
+    prefix: &'a str,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Cache {
+    size: u32,
+}
+
+fn loc(line: u32, column: u32) -> Location {
+    Location { line, column }
+}
+
+const PLUGINS: &str = r#"[
+    (type: "logger", config: (level: 3, prefix: "app")),
+    (
+        type: "cache",
+        config: (
+            size: -1,
+        ),
+    ),
+]"#;
+
+#[test]
+fn deferred_by_type() {
+    let plugins: Vec<Plugin> = from_str_serde(PLUGINS).unwrap();
+
+    assert_eq!(plugins[0].r#type, "logger");
+    assert_eq!(plugins[0].config.get(), r#"(level: 3, prefix: "app")"#);
+    assert_eq!(
+        (plugins[0].config.start(), plugins[0].config.end()),
+        (loc(2, 30), loc(2, 55))
+    );
+    // Borrows from the original document
+    assert_eq!(
+        plugins[0].config.deserialize_into::<Logger>().unwrap(),
+        Logger {
+            level: 3,
+            prefix: "app"
+        }
+    );
+
+    assert_eq!(plugins[1].r#type, "cache");
+    assert_eq!(
+        plugins[1].config.get(),
+        "(\n            size: -1,\n        )"
+    );
+    let e = plugins[1].config.deserialize_into::<Cache>().unwrap_err();
+    assert_eq!(e.start(), Some(loc(6, 19)));
+    assert_eq!(e.path().unwrap().to_string(), "size");
+}
+
+#[test]
+fn errors_render_with_the_document() {
+    let mut files = Files::new();
+    let id = files.add("plugins.ron", PLUGINS);
+
+    let plugins: Vec<Plugin> = from_str_serde(files.source(id)).unwrap();
+    let e = plugins[1]
+        .config
+        .deserialize_into::<Cache>()
+        .unwrap_err()
+        .context_file(id);
+
+    let mut rendered = vec![];
+    files.write_error(&mut rendered, &e).unwrap();
+
+    assert!(String::from_utf8(rendered).unwrap().contains(
+        " --> plugins.ron:6:19
+  |
+6 |             size: -1,
+  |                   ^^
+"
+    ));
+}
+
+#[test]
+fn nested() {
+    #[derive(Deserialize)]
+    struct Outer<'a> {
+        #[serde(borrow)]
+        inner: RawRon<'a>,
+    }
+
+    let outer: Outer = from_str_serde("(inner: (\n  inner: [1, 2]))").unwrap();
+    let inner: Outer = outer.inner.deserialize_into().unwrap();
+
+    assert_eq!(inner.inner.get(), "[1, 2]");
+    assert_eq!(inner.inner.start(), loc(2, 10));
+    assert_eq!(inner.inner.deserialize_into::<Vec<u8>>().unwrap(), [1, 2]);
+}
+
+#[test]
+fn after_non_ascii_chars() {
+    let plugin: Plugin =
+        from_str_serde("(type: \"größe\", config: (\n  prefix: \"ä\", level: 1))").unwrap();
+
+    assert_eq!(plugin.config.get(), "(\n  prefix: \"ä\", level: 1)");
+    let logger: Logger = plugin.config.deserialize_into().unwrap();
+    assert_eq!(logger.prefix, "ä");
+
+    #[derive(Deserialize)]
+    struct Outer<'a> {
+        #[serde(borrow)]
+        inner: RawRon<'a>,
+    }
+
+    let outer: Outer = from_str_serde("(inner: (ö: 1, inner: \"ü\"))").unwrap();
+    let inner: Outer = outer.inner.deserialize_into().unwrap();
+    assert_eq!(inner.inner.get(), "\"ü\"");
+}
+
+#[test]
+fn owned() {
+    #[derive(Deserialize)]
+    struct Plugin {
+        config: RawRonBuf,
+    }
+
+    let plugin: Plugin = from_reader("(config: (size: 7))".as_bytes()).unwrap();
+
+    assert_eq!(plugin.config.get(), "(size: 7)");
+    assert_eq!(plugin.config.start(), loc(1, 10));
+    assert_eq!(
+        plugin.config.deserialize_into::<Cache>().unwrap(),
+        Cache { size: 7 }
+    );
+    assert_eq!(plugin.config.as_raw_ron().to_raw_ron_buf(), plugin.config);
+}
+
+#[test]
+fn needs_the_source() {
+    let ron = ast_from_str("(type: \"cache\", config: (size: 7))").unwrap();
+
+    let e = Plugin::deserialize(RonDeserializer::from_ron_with_options(
+        &ron,
+        Default::default(),
+    ))
+    .unwrap_err();
+
+    assert_eq!(
+        e.kind.to_string(),
+        "`RawRon` can only be deserialized from a string"
+    );
+    assert_eq!(e.start(), Some(loc(1, 25)));
+}
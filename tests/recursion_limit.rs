@@ -0,0 +1,207 @@
+#![cfg(test)]
+
+#[cfg(feature = "value")]
+use ron_reboot::Value;
+use ron_reboot::{
+    from_str_serde,
+    utf8_parser::{
+        ast_from_str, ast_from_str_with_recursion_limit,
+        serde::{from_str_with_options, Options, RonDeserializer},
+        DEFAULT_RECURSION_LIMIT,
+    },
+    Location,
+};
+use serde::Deserialize;
+
+/// Openers of all expressions which can contain other expressions
+const OPENERS: &[&str] = &["[", "(", "Some(", "Tagged(", "{1: ", "(a: ", "X(b: "];
+
+/// The closing delimiters of [`OPENERS`]
+const CLOSERS: &[&str] = &["]", ")", ")", ")", "}", ")", ")"];
+
+/// Deterministic pseudo random nesting of all container kinds, without closing delimiters
+fn nested_openers(depth: usize, mut seed: u64) -> String {
+    (0..depth)
+        .map(|_| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+
+            OPENERS[(seed >> 33) as usize % OPENERS.len()]
+        })
+        .collect()
+}
+
+/// Like [`nested_openers`], but closed around `innermost`
+fn nested_document(depth: usize, mut seed: u64, innermost: &str) -> String {
+    let kinds: Vec<usize> = (0..depth)
+        .map(|_| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+
+            (seed >> 33) as usize % OPENERS.len()
+        })
+        .collect();
+
+    let openers: String = kinds.iter().map(|&k| OPENERS[k]).collect();
+    let closers: String = kinds.iter().rev().map(|&k| CLOSERS[k]).collect();
+
+    format!("{}{}{}", openers, innermost, closers)
+}
+
+/// A list of lists, to exercise the deserializer without the `value` feature
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+struct Lists(Vec<Lists>);
+
+impl Lists {
+    fn depth(&self) -> usize {
+        1 + self.0.iter().map(Lists::depth).max().unwrap_or_default()
+    }
+}
+
+fn is_recursion_error(e: &ron_reboot::Error) -> bool {
+    e.to_string().contains("exceeded recursion limit")
+}
+
+// These tests run on the test harness threads, which have the default stack size
+
+#[cfg(feature = "value")]
+#[test]
+fn deeply_nested_input_errors() {
+    let e = ast_from_str(&"[".repeat(100_000)).unwrap_err();
+    assert!(is_recursion_error(&e), "{}", e);
+    assert_eq!(
+        e.start(),
+        Some(Location {
+            line: 1,
+            column: DEFAULT_RECURSION_LIMIT as u32 + 2
+        })
+    );
+
+    for seed in 0..64 {
+        let input = nested_openers(10_000, seed);
+
+        let e = ast_from_str(&input).unwrap_err();
+        assert!(is_recursion_error(&e), "seed {}: {}", seed, e);
+
+        let e = from_str_serde::<Value>(&input).unwrap_err();
+        assert!(is_recursion_error(&e), "seed {}: {}", seed, e);
+    }
+}
+
+#[cfg(feature = "value")]
+#[test]
+fn default_limit_fits_the_stack() {
+    for (opener, closer) in OPENERS.iter().zip(CLOSERS) {
+        let input = opener.repeat(100_000);
+        let e = ast_from_str(&input).unwrap_err();
+        assert!(is_recursion_error(&e), "{}: {}", opener, e);
+
+        let input = format!(
+            "{}1{}",
+            opener.repeat(DEFAULT_RECURSION_LIMIT),
+            closer.repeat(DEFAULT_RECURSION_LIMIT)
+        );
+        from_str_serde::<Value>(&input).unwrap();
+    }
+}
+
+#[cfg(feature = "value")]
+#[test]
+fn limit_counts_containers() {
+    let within = format!("{}1{}", "[".repeat(3), "]".repeat(3));
+    let exceeding = format!("[{}]", within);
+
+    assert!(ast_from_str_with_recursion_limit(&within, Some(3)).is_ok());
+    assert!(ast_from_str_with_recursion_limit(&exceeding, Some(3)).is_err());
+    assert!(ast_from_str_with_recursion_limit(&exceeding, None).is_ok());
+
+    let options = Options::default().recursion_limit(Some(3));
+    assert!(from_str_with_options::<Value>(&within, options).is_ok());
+    assert!(is_recursion_error(
+        &from_str_with_options::<Value>(&exceeding, options).unwrap_err()
+    ));
+}
+
+#[cfg(feature = "value")]
+#[test]
+fn deserializer_enforces_limit() {
+    let input = format!("{}{}", "[".repeat(10), "]".repeat(10));
+    let ron = ast_from_str(&input).unwrap();
+
+    let e = Value::deserialize(RonDeserializer::from_ron_with_options(
+        &ron,
+        Options::default().recursion_limit(Some(5)),
+    ))
+    .unwrap_err();
+    assert!(is_recursion_error(&e), "{}", e);
+}
+
+#[test]
+fn parser_and_deserializer_agree_on_lists() {
+    for limit in 0..4 {
+        // the limit counts containers, even if the innermost one is empty
+        for depth in 1..6 {
+            let input = format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+            let within = depth <= limit;
+
+            let parsed = ast_from_str_with_recursion_limit(&input, Some(limit));
+            assert_eq!(parsed.is_ok(), within, "{} with limit {}", input, limit);
+
+            let ron = ast_from_str_with_recursion_limit(&input, None).unwrap();
+            let deserialized = Lists::deserialize(RonDeserializer::from_ron_with_options(
+                &ron,
+                Options::default().recursion_limit(Some(limit)),
+            ));
+            match deserialized {
+                Ok(lists) => assert!(
+                    within && lists.depth() == depth,
+                    "{} with limit {}",
+                    input,
+                    limit
+                ),
+                Err(e) => assert!(
+                    !within && is_recursion_error(&e),
+                    "{} with limit {}: {}",
+                    input,
+                    limit,
+                    e
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "value")]
+#[test]
+fn parser_and_deserializer_agree() {
+    for limit in 0..4 {
+        for depth in 0..6 {
+            for seed in 0..16 {
+                let input = nested_document(depth, seed, "1");
+                let within = depth <= limit;
+
+                let parsed = ast_from_str_with_recursion_limit(&input, Some(limit));
+                assert_eq!(parsed.is_ok(), within, "{} with limit {}", input, limit);
+
+                let ron = ast_from_str_with_recursion_limit(&input, None).unwrap();
+                let deserialized = Value::deserialize(RonDeserializer::from_ron_with_options(
+                    &ron,
+                    Options::default().recursion_limit(Some(limit)),
+                ));
+                match deserialized {
+                    Ok(_) => assert!(within, "{} with limit {}", input, limit),
+                    Err(e) => assert!(
+                        !within && is_recursion_error(&e),
+                        "{} with limit {}: {}",
+                        input,
+                        limit,
+                        e
+                    ),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use ron_reboot::{utf8_parser::ast_from_str, ColorChoice, Error, Files, Location, Renderer};
+
+fn loc(line: u32, column: u32) -> Location {
+    Location { line, column }
+}
+
+fn render(renderer: Renderer, e: &Error) -> String {
+    let mut rendered = String::new();
+    renderer.fmt_error(&mut rendered, e).unwrap();
+
+    rendered
+}
+
+#[test]
+fn tabs_and_wide_chars() {
+    let e = Error::from(std::io::Error::other("boom"))
+        .context_file_content("\t\"日本\" x".to_owned())
+        .context_loc(loc(1, 6), loc(1, 7));
+
+    assert_eq!(
+        render(Renderer::new(), &e),
+        "error: io error: boom
+ --> string:1:6
+  |
+1 |     \"日本\" x
+  |           ^
+  |
+"
+    );
+    assert_eq!(
+        render(Renderer::new().tab_width(1), &e),
+        "error: io error: boom
+ --> string:1:6
+  |
+1 |  \"日本\" x
+  |        ^
+  |
+"
+    );
+}
+
+#[test]
+fn wide_chars_are_underlined_fully() {
+    let e = Error::from(std::io::Error::other("boom"))
+        .context_file_content("(a: \"フェリス\")".to_owned())
+        .context_loc(loc(1, 5), loc(1, 10))
+        .context_label(loc(1, 6), loc(1, 7), "first char");
+
+    assert_eq!(
+        render(Renderer::new(), &e),
+        "error: io error: boom
+ --> string:1:5
+  |
+1 | (a: \"フェリス\")
+  |     ^^^^^^^^^
+  |
+1 | (a: \"フェリス\")
+  |      -- first char
+  |
+"
+    );
+}
+
+#[test]
+fn notes_and_help() {
+    let mut files = Files::new();
+    let id = files.add("config.ron", "(a: 1 2)");
+
+    let e = ast_from_str(files.source(id))
+        .unwrap_err()
+        .context_file(id)
+        .context_note("struct fields are separated by `,`")
+        .context_help("add a `,` after `1`");
+    assert_eq!(e.notes().len(), 2);
+
+    assert!(
+        render(Renderer::new().files(&files), &e).ends_with(
+            "  |
+  = note: struct fields are separated by `,`
+  = help: add a `,` after `1`
+"
+        ),
+        "{}",
+        render(Renderer::new().files(&files), &e)
+    );
+
+    // Notes are printed without a snippet as well
+    let e = Error::from(std::io::Error::other("boom")).context_help("try again");
+    assert_eq!(
+        render(Renderer::new(), &e),
+        "error: io error: boom\n = help: try again\n"
+    );
+}
+
+#[test]
+fn colors() {
+    let e = Error::from(std::io::Error::other("boom"))
+        .context_file_content("[1, 2".to_owned())
+        .context_loc(loc(1, 6), loc(1, 6));
+
+    let plain = render(Renderer::new(), &e);
+    assert!(!plain.contains('\x1b'));
+
+    let colored = render(Renderer::new().color(ColorChoice::Always), &e);
+    assert!(
+        colored.starts_with("\x1b[1;31merror\x1b[0m"),
+        "{:?}",
+        colored
+    );
+    assert!(colored.contains("\x1b[1;34m -->\x1b[0m"));
+
+    let mut written = vec![];
+    Renderer::new()
+        .color(ColorChoice::Always)
+        .write_with_severity(&mut written, "warning", &e)
+        .unwrap();
+    assert!(String::from_utf8(written)
+        .unwrap()
+        .starts_with("\x1b[1;33mwarning\x1b[0m"));
+}
+
+#[test]
+fn severity_without_source() {
+    let e = ast_from_str("[1, 2").unwrap_err();
+
+    let mut written = vec![];
+    Renderer::new()
+        .write_with_severity(&mut written, "warning", &e)
+        .unwrap();
+    assert!(String::from_utf8(written)
+        .unwrap()
+        .starts_with("warning at 1:6: "));
+}
+
+#[test]
+fn label_on_line_zero() {
+    // e.g. a hand-built label without a real location
+    let e = ast_from_str("[1, 2")
+        .unwrap_err()
+        .context_file_content("[1, 2".to_owned())
+        .context_label(loc(0, 0), loc(0, 0), "unknown location");
+
+    assert!(render(Renderer::new(), &e).contains("unknown location"));
+}
@@ -0,0 +1,72 @@
+use ron_reboot::{from_str_serde, Error, Files, Location, Spanned};
+use serde::{de::Error as _, Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Server {
+    name: Spanned<String>,
+    port: Spanned<u16>,
+    backup: Option<Spanned<u16>>,
+}
+
+fn loc(line: u32, column: u32) -> Location {
+    Location { line, column }
+}
+
+#[test]
+fn fields_keep_locations() {
+    let server: Server =
+        from_str_serde("(\n    name: \"main\",\n    port: 80,\n    backup: Some(80),\n)").unwrap();
+
+    assert_eq!(server.name.value, "main");
+    assert_eq!(
+        (server.name.start, server.name.end),
+        (loc(2, 11), loc(2, 17))
+    );
+    assert_eq!(
+        (server.port.start, server.port.end),
+        (loc(3, 11), loc(3, 13))
+    );
+
+    let backup = server.backup.unwrap();
+    assert_eq!((backup.start, backup.end), (loc(4, 18), loc(4, 20)));
+}
+
+#[test]
+fn checks_after_deserialization() {
+    let mut files = Files::new();
+    let id = files.add("server.ron", "(name: \"main\", port: 80, backup: Some(80))");
+
+    let server: Server = from_str_serde(files.source(id)).unwrap();
+    let backup = server.backup.unwrap();
+    assert_eq!(backup.value, server.port.value);
+
+    let e = Error::custom("the backup port must differ from the port")
+        .context_file(id)
+        .context_loc(backup.start, backup.end)
+        .context_label(server.port.start, server.port.end, "port defined here");
+
+    let mut rendered = vec![];
+    files.write_error(&mut rendered, &e).unwrap();
+
+    assert_eq!(
+        String::from_utf8(rendered).unwrap(),
+        "error: the backup port must differ from the port
+ --> server.ron:1:39
+  |
+1 | (name: \"main\", port: 80, backup: Some(80))
+  |                                       ^^
+  |
+1 | (name: \"main\", port: 80, backup: Some(80))
+  |                      -- port defined here
+  |
+"
+    );
+}
+
+#[test]
+fn errors_inside_spanned() {
+    let e = from_str_serde::<Server>("(name: \"main\", port: -1)").unwrap_err();
+
+    assert_eq!(e.start(), Some(loc(1, 22)));
+    assert_eq!(e.path().unwrap().to_string(), "port");
+}
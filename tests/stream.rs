@@ -0,0 +1,199 @@
+use std::io::Read;
+
+use ron_reboot::{
+    utf8_parser::serde::{ReaderStreamDeserializer, StreamDeserializer},
+    Error, Location,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Event<'a> {
+    id: u32,
+    #[serde(borrow)]
+    name: &'a str,
+}
+
+fn loc(line: u32, column: u32) -> Location {
+    Location { line, column }
+}
+
+const LOG: &str = r#"(id: 1, name: "start")
+(id: 2, name: "tick") (id: 3, name: "tick")
+// a comment between documents
+
+(
+    id: 4,
+    name: "stop",
+)
+"#;
+
+#[test]
+fn consecutive_documents() {
+    let events: Vec<Event> = StreamDeserializer::new(LOG)
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(
+        events.iter().map(|e| (e.id, e.name)).collect::<Vec<_>>(),
+        [(1, "start"), (2, "tick"), (3, "tick"), (4, "stop")]
+    );
+
+    assert_eq!(StreamDeserializer::<u32>::new("").count(), 0);
+    assert_eq!(StreamDeserializer::<u32>::new("  // nothing\n").count(), 0);
+}
+
+#[test]
+fn errors_point_into_the_stream() {
+    let mut stream = StreamDeserializer::<Event>::new(
+        "(id: 1, name: \"a\")\n(id: -2, name: \"b\")\n(id: 3, name: \"c\")",
+    );
+
+    assert_eq!(stream.next().unwrap().unwrap().id, 1);
+
+    let e = stream.next().unwrap().unwrap_err();
+    assert_eq!(e.start(), Some(loc(2, 6)));
+    assert_eq!(e.path().unwrap().to_string(), "id");
+
+    // A value of the wrong type doesn't affect the next document
+    assert_eq!(stream.next().unwrap().unwrap().id, 3);
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn recovers_after_syntax_errors() {
+    let source =
+        "(id: 1, name: \"a\"\n(\n    id: 2,\n    name: \"b\" \"c\",\n)\n(id: 3, name: \"d\")\n";
+    let results: Vec<Result<Event, Error>> = StreamDeserializer::new(source).collect();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap_err().start(), Some(loc(2, 1)));
+    assert_eq!(results[1].as_ref().unwrap_err().start(), Some(loc(4, 15)));
+    assert_eq!(results[2].as_ref().unwrap(), &Event { id: 3, name: "d" });
+}
+
+#[test]
+fn attributes_per_document() {
+    let values: Vec<Option<u32>> =
+        StreamDeserializer::new("#![enable(implicit_some)]\n1\nSome(2)\n")
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+    assert_eq!(values, [Some(1), Some(2)]);
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct OwnedEvent {
+    id: u32,
+    name: String,
+}
+
+#[test]
+fn reader() {
+    let events: Vec<OwnedEvent> = ReaderStreamDeserializer::new(LOG.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(events.len(), 4);
+    assert_eq!(
+        events[3],
+        OwnedEvent {
+            id: 4,
+            name: "stop".to_owned()
+        }
+    );
+
+    let source =
+        "(id: 1, name: \"a\"\n(\n    id: 2,\n    name: \"b\" \"c\",\n)\n(id: 3, name: \"d\")";
+    let results: Vec<Result<OwnedEvent, Error>> =
+        ReaderStreamDeserializer::new(source.as_bytes()).collect();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap_err().start(), Some(loc(2, 1)));
+    assert_eq!(results[1].as_ref().unwrap_err().start(), Some(loc(4, 15)));
+    assert_eq!(results[2].as_ref().unwrap().id, 3);
+
+    // An unfinished document at the end is an error
+    let results: Vec<Result<OwnedEvent, Error>> =
+        ReaderStreamDeserializer::new("(id: 1, name: \"a\")\n(id: 2,\n".as_bytes()).collect();
+    assert_eq!(results.len(), 2);
+    assert!(results[1].is_err());
+}
+
+#[test]
+fn reader_named_structs() {
+    let source = "OwnedEvent(\n    id: 1,\n    name: \"a\",\n)\nOwnedEvent(id: 2, name: \"b\")\n";
+    let events: Vec<OwnedEvent> = ReaderStreamDeserializer::new(source.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(
+        events[0],
+        OwnedEvent {
+            id: 1,
+            name: "a".to_owned()
+        }
+    );
+    assert_eq!(events[1].id, 2);
+
+    // A named struct without its `)` is an error at the next document
+    let source = "OwnedEvent(id: 1, name: \"a\"\nOwnedEvent(id: 2, name: \"b\")\n";
+    let results: Vec<Result<OwnedEvent, Error>> =
+        ReaderStreamDeserializer::new(source.as_bytes()).collect();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap_err().start(), Some(loc(2, 1)));
+    assert_eq!(results[1].as_ref().unwrap().id, 2);
+}
+
+/// Yields the lines of `source` one by one and fails afterwards
+struct FailingReader(&'static str);
+
+impl Read for FailingReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.0.find('\n') {
+            Some(end) => {
+                let (line, rest) = self.0.split_at(end + 1);
+                buf[..line.len()].copy_from_slice(line.as_bytes());
+                self.0 = rest;
+
+                Ok(line.len())
+            }
+            None => Err(std::io::Error::other("connection reset")),
+        }
+    }
+}
+
+#[test]
+fn reader_values_are_available_early() {
+    let mut stream = ReaderStreamDeserializer::<_, u32>::new(FailingReader("1\n2\n"));
+
+    assert_eq!(stream.next().unwrap().unwrap(), 1);
+    assert_eq!(stream.next().unwrap().unwrap(), 2);
+    assert_eq!(
+        stream.next().unwrap().unwrap_err().to_string(),
+        "error: io error: connection reset"
+    );
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn reader_max_lines() {
+    let source = "(id: 1,\n    name: \"a\",\n\n\n(id: 2, name: \"b\")\n";
+    let results: Vec<Result<OwnedEvent, Error>> = ReaderStreamDeserializer::new(source.as_bytes())
+        .max_lines(Some(3))
+        .collect();
+
+    assert_eq!(results.len(), 2);
+    let e = results[0].as_ref().unwrap_err();
+    assert_eq!(e.start(), Some(loc(4, 1)));
+    assert!(format!("{:?}", e).contains("the document is unfinished after 3 lines"));
+    assert_eq!(results[1].as_ref().unwrap().id, 2);
+
+    // Without the limit, the document continues until the next value
+    let results: Vec<Result<OwnedEvent, Error>> = ReaderStreamDeserializer::new(source.as_bytes())
+        .max_lines(None)
+        .collect();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap_err().start(), Some(loc(5, 1)));
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "value")]
+
+use proptest::prelude::*;
+use ron_reboot::{value::Number, Value};
+
+/// Names of units, tuples and structs, some of which need to be raw identifiers
+fn name() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[A-Za-z_][A-Za-z0-9_]{0,8}",
+        "[äöüßλ][a-z0-9äöüλ]{0,4}",
+        "[a-z]{1,4}-[a-z]{1,4}",
+        prop_oneof![
+            Just("Some"),
+            Just("None"),
+            Just("true"),
+            Just("false"),
+            Just("inf"),
+            Just("NaN"),
+        ]
+        .prop_map(str::to_owned),
+    ]
+}
+
+fn leaf() -> impl Strategy<Value = Value> {
+    prop_oneof![
+        any::<bool>().prop_map(Value::Bool),
+        any::<char>().prop_map(Value::Char),
+        any::<i64>().prop_map(|i| Value::Number(Number::new(i))),
+        any::<f64>().prop_map(|f| Value::Number(Number::new(f))),
+        any::<String>().prop_map(Value::String),
+        Just(Value::Option(None)),
+        proptest::option::of(name()).prop_map(Value::Unit),
+    ]
+}
+
+fn value() -> impl Strategy<Value = Value> {
+    leaf().prop_recursive(4, 64, 6, |inner| {
+        let elements = |min| proptest::collection::vec(inner.clone(), min..6);
+        let fields = proptest::collection::vec((name(), inner.clone()), 1..6);
+
+        prop_oneof![
+            inner.clone().prop_map(|v| Value::Option(Some(Box::new(v)))),
+            elements(0).prop_map(Value::List),
+            proptest::collection::vec((inner.clone(), inner.clone()), 0..6).prop_map(Value::Map),
+            // `()` is the unit value, not an empty tuple or struct
+            elements(1).prop_map(|elements| Value::Tuple(None, elements)),
+            (name(), elements(0)).prop_map(|(name, elements)| Value::Tuple(Some(name), elements)),
+            (proptest::option::of(name()), fields)
+                .prop_map(|(name, fields)| Value::Struct(name, fields)),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn display_parse_roundtrip(value in value()) {
+        let printed = value.to_string();
+        let parsed = printed.parse::<Value>().map_err(|e| TestCaseError::fail(e.to_string()))?;
+        prop_assert_eq!(&parsed, &value, "printed as {}", printed);
+
+        let pretty = format!("{:#}", value);
+        let parsed = pretty.parse::<Value>().map_err(|e| TestCaseError::fail(e.to_string()))?;
+        prop_assert_eq!(&parsed, &value, "printed as {}", pretty);
+    }
+}